@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+
+use crate::error::Error;
+use crate::helpers;
+
+pub const DEFAULT_ANSWERS_FILE: &str = "./src/resources/answers.txt";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong
+}
+
+#[derive(Debug)]
+pub struct KnownAnswer {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub verdict: Verdict,
+    pub value: String,
+    pub note: Option<String>
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome<'a> {
+    Pass,
+    Mismatch { expected: &'a str },
+    Unknown
+}
+
+/*
+    Recorded answers per day, part and input file, including guesses that were rejected.
+ */
+pub struct Answers {
+    entries: Vec<KnownAnswer>
+}
+
+impl Answers {
+
+    pub fn load(answers_file_name: &str) -> Result<Answers, Error> {
+        Answers::parse(&helpers::read_input(answers_file_name)?)
+    }
+
+    pub fn parse(input: &str) -> Result<Answers, Error> {
+        let mut entries: Vec<KnownAnswer> = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let bad_line = || Error::BadLine {
+                line: idx + 1,
+                content: line.to_string(),
+                expected: "<day> <part> <input> correct|wrong <answer> [note]"
+            };
+            // The first five fields can be padded to line up, the note is the rest of the line
+            let mut fields: Vec<(usize, &str)> = Vec::new();
+            let mut rest = line;
+            while fields.len() < 5 {
+                let trimmed = rest.trim_start();
+                if trimmed.is_empty() {
+                    return Err(bad_line());
+                }
+                let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+                fields.push((line.len() - trimmed.len() + 1, &trimmed[..end]));
+                rest = &trimmed[end..];
+            }
+            let note = rest.trim();
+            let verdict = match fields[3].1 {
+                "correct" => Verdict::Correct,
+                "wrong" => Verdict::Wrong,
+                _ => return Err(bad_line())
+            };
+            entries.push(KnownAnswer {
+                day: helpers::parse_number(fields[0].1, idx + 1, fields[0].0)?,
+                part: helpers::parse_number(fields[1].1, idx + 1, fields[1].0)?,
+                input: fields[2].1.to_string(),
+                verdict,
+                value: fields[4].1.to_string(),
+                note: if note.is_empty() { None } else { Some(note.to_string()) }
+            });
+        }
+        Ok(Answers { entries })
+    }
+
+    /*
+        Every input file with recorded answers for the day, in name order
+     */
+    pub fn inputs(&self, day: u8) -> Vec<&str> {
+        self.entries.iter()
+            .filter(|entry| entry.day == day)
+            .map(|entry| entry.input.as_str())
+            .collect::<BTreeSet<&str>>()
+            .into_iter()
+            .collect()
+    }
+
+    pub fn correct(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.matching(day, part, input).into_iter()
+            .find(|entry| entry.verdict == Verdict::Correct)
+            .map(|entry| entry.value.as_str())
+    }
+
+    pub fn rejected(&self, day: u8, part: u8, input: &str, value: &str) -> Option<&KnownAnswer> {
+        self.matching(day, part, input).into_iter()
+            .find(|entry| entry.verdict == Verdict::Wrong && entry.value == value)
+    }
+
+    pub fn check(&self, day: u8, part: u8, input: &str, value: &str) -> Outcome<'_> {
+        match self.correct(day, part, input) {
+            Some(expected) if expected == value => Outcome::Pass,
+            Some(expected) => Outcome::Mismatch { expected },
+            None => Outcome::Unknown
+        }
+    }
+
+    fn matching(&self, day: u8, part: u8, input: &str) -> Vec<&KnownAnswer> {
+        self.entries.iter()
+            .filter(|entry| entry.day == day && entry.part == part && entry.input == input)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "# comment
+3 2 day03_input.txt wrong 10046858 too low
+3 2 day03_input.txt correct 100189366
+6 2 day06_input.txt wrong 2092 too high
+";
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+        assert_eq!(Outcome::Pass, answers.check(3, 2, "day03_input.txt", "100189366"));
+        assert_eq!(Outcome::Mismatch { expected: "100189366" }, answers.check(3, 2, "day03_input.txt", "5"));
+        assert_eq!(Outcome::Unknown, answers.check(6, 2, "day06_input.txt", "1234"));
+        assert_eq!(Outcome::Unknown, answers.check(3, 1, "day03_input.txt", "5"));
+    }
+
+    #[test]
+    fn test_rejected() {
+        let answers = Answers::parse(SAMPLE).unwrap();
+        let rejected = answers.rejected(6, 2, "day06_input.txt", "2092").unwrap();
+        assert_eq!(Some("too high".to_string()), rejected.note);
+        assert!(answers.rejected(6, 2, "day06_input.txt", "2091").is_none());
+    }
+
+    #[test]
+    fn test_aligned_line() {
+        let answers = Answers::parse("3  2 day03_input.txt  wrong    10046858  too  low\n3 2 day03_input.txt correct 100189366   ").unwrap();
+        let rejected = answers.rejected(3, 2, "day03_input.txt", "10046858").unwrap();
+        assert_eq!(Some("too  low".to_string()), rejected.note);
+        assert_eq!(Outcome::Pass, answers.check(3, 2, "day03_input.txt", "100189366"));
+        assert!(matches!(Answers::parse("3  x day03_input.txt correct 5"), Err(Error::BadNumber { line: 1, column: 4, .. })));
+    }
+
+    #[test]
+    fn test_bad_line() {
+        assert!(matches!(Answers::parse("3 2 day03_input.txt maybe 5"), Err(Error::BadLine { line: 1, .. })));
+        assert!(matches!(Answers::parse("\nx 2 day03_input.txt correct 5"), Err(Error::BadNumber { line: 2, column: 1, .. })));
+    }
+
+    #[test]
+    fn test_answers_file() {
+        let answers = Answers::load(DEFAULT_ANSWERS_FILE).unwrap();
+        assert_eq!(vec!["day01_input.txt", "day01_simple.txt"], answers.inputs(1));
+    }
+}
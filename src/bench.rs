@@ -0,0 +1,114 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::Error;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration
+}
+
+impl Stats {
+
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1]
+        }
+    }
+
+    fn to_json(self) -> String {
+        format!("{{\"min_ns\":{},\"median_ns\":{},\"max_ns\":{}}}",
+            self.min.as_nanos(), self.median.as_nanos(), self.max.as_nanos())
+    }
+}
+
+/*
+    Timings for one day.  A part that isn't solved yet has no timings.
+ */
+pub struct Timings {
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part_one: Option<Stats>,
+    pub part_two: Option<Stats>
+}
+
+impl Timings {
+
+    pub fn to_json(&self, day: u8, title: &str) -> String {
+        let optional = |stats: Option<Stats>| stats.map_or("null".to_string(), Stats::to_json);
+        format!("{{\"day\":{},\"title\":\"{}\",\"iterations\":{},\"parse\":{},\"part_one\":{},\"part_two\":{}}}",
+            day, title, self.iterations, self.parse.to_json(), optional(self.part_one), optional(self.part_two))
+    }
+}
+
+fn time<T, F: FnMut() -> T>(mut f: F) -> (Duration, T) {
+    let start = Instant::now();
+    let value = black_box(f());
+    (start.elapsed(), value)
+}
+
+/*
+    Parses the input and runs each part once per iteration, timing every stage separately.
+ */
+pub fn time_solution<S: Solution>(input: &str, iterations: usize) -> Result<Timings, Error> {
+    let iterations = iterations.max(1);
+    let mut parse_samples: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part_one_samples: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part_two_samples: Vec<Duration> = Vec::with_capacity(iterations);
+
+    for _ in 0..iterations {
+        let (elapsed, solution) = time(|| S::parse(black_box(input)));
+        let solution = solution?;
+        parse_samples.push(elapsed);
+
+        let (elapsed, answer) = time(|| solution.part_one());
+        let answer = answer?;
+        if answer.is_some() {
+            part_one_samples.push(elapsed);
+        }
+        let (elapsed, answer) = time(|| solution.part_two());
+        let answer = answer?;
+        if answer.is_some() {
+            part_two_samples.push(elapsed);
+        }
+    }
+
+    let optional = |samples: &mut Vec<Duration>| if samples.is_empty() { None } else { Some(Stats::from_samples(samples)) };
+    Ok(Timings {
+        iterations,
+        parse: Stats::from_samples(&mut parse_samples),
+        part_one: optional(&mut part_one_samples),
+        part_two: optional(&mut part_two_samples)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let mut samples = vec![Duration::from_millis(5), Duration::from_millis(1), Duration::from_millis(3)];
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(Duration::from_millis(1), stats.min);
+        assert_eq!(Duration::from_millis(3), stats.median);
+        assert_eq!(Duration::from_millis(5), stats.max);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let stats = Stats { min: Duration::from_nanos(1), median: Duration::from_nanos(2), max: Duration::from_nanos(3) };
+        let timings = Timings { iterations: 3, parse: stats, part_one: Some(stats), part_two: None };
+        assert_eq!(
+            "{\"day\":4,\"title\":\"Ceres Search\",\"iterations\":3,\
+            \"parse\":{\"min_ns\":1,\"median_ns\":2,\"max_ns\":3},\
+            \"part_one\":{\"min_ns\":1,\"median_ns\":2,\"max_ns\":3},\"part_two\":null}",
+            timings.to_json(4, "Ceres Search"));
+    }
+}
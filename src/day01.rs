@@ -1,30 +1,68 @@
+use crate::error::Error;
 use crate::helpers;
+use crate::registry::Status;
+use crate::solution::Solution;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
 
-fn load_lists(input_file_name: &str) -> (Vec<u32>, Vec<u32>) {
-    static VALUES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)\s*(\d+)").unwrap());
+fn load_lists(input: &str) -> Result<(Vec<u32>, Vec<u32>), Error> {
+    static VALUES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^\s*(\d+)\s+(\d+)\s*$").unwrap());
 
     let mut first_list: Vec<u32> = Vec::new();
     let mut second_list: Vec<u32> = Vec::new();
 
-    if let Ok(lines) = helpers::read_lines(input_file_name) {
-        for line in lines.flatten() {
-            for c in VALUES_RE.captures_iter(&line) {
-                let (_, [number1, number2]) = c.extract();
-                match number1.parse() {
-                    Ok(number) => first_list.push(number),
-                    Err(_) => println!("Unable to parse {}", number1)
-                };
-                match number2.parse() {
-                    Ok(number) => second_list.push(number),
-                    Err(_) => println!("Unable to parse {}", number2)
-                };
-            }
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
         }
+        let capture = VALUES_RE.captures(line).ok_or_else(|| Error::BadLine {
+            line: idx + 1,
+            content: line.to_string(),
+            expected: "two numbers"
+        })?;
+        let number1 = capture.get(1).unwrap();
+        let number2 = capture.get(2).unwrap();
+        first_list.push(helpers::parse_number(number1.as_str(), idx + 1, number1.start() + 1)?);
+        second_list.push(helpers::parse_number(number2.as_str(), idx + 1, number2.start() + 1)?);
+    }
+    Ok((first_list, second_list))
+}
+
+pub struct Day01 {
+    first_list: Vec<u32>,
+    second_list: Vec<u32>
+}
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Historian Hysteria";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (first_list, second_list) = load_lists(input)?;
+        Ok(Day01 { first_list, second_list })
+    }
+
+    fn part_one(&self) -> Result<Option<u32>, Error> {
+        let mut first_list = self.first_list.clone();
+        let mut second_list = self.second_list.clone();
+        first_list.sort();
+        second_list.sort();
+
+        Ok(Some(first_list.iter().zip(second_list.iter())
+            .map(|(v1, v2)| v1.abs_diff(*v2))
+            .sum()))
+    }
+
+    fn part_two(&self) -> Result<Option<u32>, Error> {
+        Ok(Some(self.first_list.iter().fold(0, |acc, number| {
+            let occurrences = self.second_list.iter().filter(|value| *value == number).count();
+            acc + (number * occurrences as u32)
+        })))
     }
-    (first_list, second_list)
 }
 
 #[cfg(test)]
@@ -33,51 +71,42 @@ mod tests {
 
     #[test]
     fn part_one_simple() {
-        let (mut first_list, mut second_list) = load_lists("./src/resources/day01_simple.txt");
-        first_list.sort();
-        second_list.sort();
-
-        let pair_list = first_list.iter().zip(second_list.iter());
-        let diffs = pair_list.enumerate().map(|(_i, (v1, v2))| v1.abs_diff(*v2));
-        let sum = diffs.fold(0, |acc, diff| acc + diff);
-        assert_eq!(sum, 11);
+        assert_eq!(Day01::load("./src/resources/day01_simple.txt").unwrap().part_one().unwrap().unwrap(), 11);
     }
 
     #[test]
     fn part_two_simple() {
-        let (first_list, second_list) = load_lists("./src/resources/day01_simple.txt");
+        assert_eq!(Day01::load("./src/resources/day01_simple.txt").unwrap().part_two().unwrap().unwrap(), 31);
+    }
 
-        let sum = first_list.iter().fold(0, |acc, number| {
-            let occurrences = second_list.iter().filter(|value| *value == number).count();
-            acc + (number * occurrences as u32)
-        });
-        assert_eq!(sum, 31);
+    #[test]
+    fn inline_example() {
+        let lists = Day01::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        assert_eq!(Some(11), lists.part_one().unwrap());
+        assert_eq!(Some(31), lists.part_two().unwrap());
     }
 
     #[test]
-    fn part_one() {
-        let (mut first_list, mut second_list) = load_lists("./src/resources/day01_input.txt");
+    fn bad_line() {
+        assert!(matches!(Day01::parse("3   4\n4\n"), Err(Error::BadLine { line: 2, .. })));
+    }
 
-        first_list.sort();
-        second_list.sort();
+    #[test]
+    fn missing_input() {
+        assert!(matches!(Day01::load("./src/resources/day01_missing.txt"), Err(Error::MissingFile { .. })));
+    }
 
-        let diffs = first_list.iter().zip(second_list.iter()).enumerate()
-            .map(|(_i, (v1, v2))| v1.abs_diff(*v2));
-        let sum = diffs.fold(0, |acc, diff| acc + diff);
+    #[test]
+    fn test_part_one() {
+        let sum = Day01::load("./src/resources/day01_input.txt").unwrap().part_one().unwrap().unwrap();
         println!("{}", sum);
         assert_eq!(sum, 1223326);
     }
 
     #[test]
-    fn part_two() {
-        let (first_list, second_list) = load_lists("./src/resources/day01_input.txt");
-
-        let sum = first_list.iter().fold(0, |acc, number| {
-            let occurrences = second_list.iter().filter(|value| *value == number).count();
-            acc + (number * occurrences as u32)
-        });
+    fn test_part_two() {
+        let sum = Day01::load("./src/resources/day01_input.txt").unwrap().part_two().unwrap().unwrap();
         println!("{}", sum);
-        // assert_eq!(sum, 31);
     }
 
 }
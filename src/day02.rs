@@ -1,23 +1,26 @@
+use crate::error::Error;
 use crate::helpers;
-use once_cell::sync::Lazy;
-use regex::Regex;
-
-fn load_reports(input_file_name: &str) -> Vec<Vec<u32>> {
-    static NUMBERS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(\d+)").unwrap());
+use crate::registry::Status;
+use crate::solution::Solution;
 
+fn load_reports(input: &str) -> Result<Vec<Vec<u32>>, Error> {
     let mut return_value: Vec<Vec<u32>> = Vec::new();
 
-    if let Ok(lines) = helpers::read_lines(input_file_name) {
-        for line in lines.flatten() {
-            let report: Vec<u32> = NUMBERS_RE.find_iter(&line).map(|value| value.as_str().parse().unwrap()).collect();
-            return_value.push(report);
+    for (idx, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let report: Vec<u32> = helpers::parse_numbers(line, idx + 1, ' ')?;
+        if report.len() < 2 {
+            return Err(Error::BadLine { line: idx + 1, content: line.to_string(), expected: "at least two levels" });
         }
+        return_value.push(report);
     }
 
-    return_value
+    Ok(return_value)
 }
 
-fn is_safe(report: Vec<u32>) -> bool {
+fn is_safe(report: &[u32]) -> bool {
 
     let mut pos = 1;
     while report[0] == report[pos] {
@@ -50,67 +53,93 @@ fn is_safe(report: Vec<u32>) -> bool {
     true
 }
 
+fn is_safe_with_dampener(report: &[u32]) -> bool {
+    if is_safe(report) {
+        return true;
+    }
+
+    let mut idx = 0;
+    while idx < report.len() {
+        let mut tweaked_report = report.to_vec();
+        tweaked_report.remove(idx);
+        if is_safe(&tweaked_report) {
+            return true;
+        }
+        idx += 1;
+    }
+    false
+}
+
+pub struct Day02 {
+    reports: Vec<Vec<u32>>
+}
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Red-Nosed Reports";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Day02 { reports: load_reports(input)? })
+    }
+
+    fn part_one(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(self.reports.iter().filter(|report| is_safe(report)).count()))
+    }
+
+    fn part_two(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(self.reports.iter().filter(|report| is_safe_with_dampener(report)).count()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn part_one_simple() {
-        let reports = load_reports("./src/resources/day02_simple.txt");
+        let reports = load_reports(include_str!("resources/day02_simple.txt")).unwrap();
         println!("{:?}", reports);
     }
 
     #[test]
     fn test_is_safe() {
-        assert_eq!(false, is_safe(vec![9,9,12,9]));
-        assert_eq!(true, is_safe(vec![7, 6, 4, 2, 1]));
-        assert_eq!(false, is_safe(vec![1, 2, 7, 8, 9]));
-        assert_eq!(false, is_safe(vec![9, 7, 6, 2, 1]));
-        assert_eq!(false, is_safe(vec![1, 3, 2, 4, 1]));
-        assert_eq!(false, is_safe(vec![8, 6, 4, 4, 1]));
-        assert_eq!(true, is_safe(vec![1, 3, 6, 7, 9]));
-        assert_eq!(false, is_safe(vec![8, 11, 14, 16, 15]));
+        assert!(!is_safe(&[9,9,12,9]));
+        assert!(is_safe(&[7, 6, 4, 2, 1]));
+        assert!(!is_safe(&[1, 2, 7, 8, 9]));
+        assert!(!is_safe(&[9, 7, 6, 2, 1]));
+        assert!(!is_safe(&[1, 3, 2, 4, 1]));
+        assert!(!is_safe(&[8, 6, 4, 4, 1]));
+        assert!(is_safe(&[1, 3, 6, 7, 9]));
+        assert!(!is_safe(&[8, 11, 14, 16, 15]));
     }
 
     #[test]
     fn test_simple_sum() {
-        let reports = load_reports("./src/resources/day02_simple.txt");
-        let safe_report_count = reports.iter().filter(|report| is_safe(report.to_vec())).count();
+        let safe_report_count = Day02::load("./src/resources/day02_simple.txt").unwrap().part_one().unwrap().unwrap();
         println!("Count {}", safe_report_count);
         assert_eq!(2, safe_report_count);
 
     }
 
     #[test]
-    fn test_sum() {
-        let reports = load_reports("./src/resources/day02_input.txt");
+    fn test_read_reports() {
+        let reports = Day02::read("7 6 4 2 1\n1 2 7 8 9\n".as_bytes()).unwrap();
+        assert_eq!(Some(1), reports.part_one().unwrap());
+    }
 
-        let safe_report_count = reports.iter().filter(|report| is_safe(report.to_vec())).count();
+    #[test]
+    fn test_sum() {
+        let safe_report_count = Day02::load("./src/resources/day02_input.txt").unwrap().part_one().unwrap().unwrap();
         println!("Count {}", safe_report_count);
         assert_eq!(472, safe_report_count);
     }
 
     #[test]
     fn test_sum_part_two() {
-        let reports = load_reports("./src/resources/day02_input.txt");
-
-        let mut safe_reports: Vec<_> = reports.iter().filter(|report| is_safe(report.to_vec())).collect();
-        let unsafe_reports: Vec<_> = reports.iter().filter(|report| !is_safe(report.to_vec())).collect();
-
-        for unsafe_report in unsafe_reports {
-            let mut idx = 0;
-            while idx < unsafe_report.len() {
-                let mut tweaked_report = unsafe_report.clone();
-                tweaked_report.remove(idx);
-                if is_safe(tweaked_report) {
-                    safe_reports.push(unsafe_report);
-                    break;
-                }
-                idx += 1;
-            }
-        }
-
-        let safe_report_count = safe_reports.len();
+        let safe_report_count = Day02::load("./src/resources/day02_input.txt").unwrap().part_two().unwrap().unwrap();
         println!("{}", safe_report_count);
         assert_eq!(520, safe_report_count);
     }
@@ -1,14 +1,19 @@
+use crate::error::Error;
+use crate::helpers;
+use crate::registry::Status;
+use crate::solution::Solution;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+static MULS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"mul\((\d+),(\d+)\)").unwrap());
+
 fn find_pairs(input: &str) -> Vec<(u32, u32)> {
     let mut return_value: Vec<(u32, u32)> = Vec::new();
-    static MULS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"mul\((\d+),(\d+)\)").unwrap());
 
     let filtered_list = filter_dont(input);
     for c in MULS_RE.captures_iter(&filtered_list) {
         let (_, [number1, number2]) = c.extract();
-        return_value.push((number1.parse().unwrap(), number2.parse().unwrap()));
+        return_value.push((number1.parse().expect("checked when loaded"), number2.parse().expect("checked when loaded")));
     }
 
 
@@ -42,36 +47,66 @@ fn filter_dont(input: &str) -> String {
     return_value
 }
 
-fn do_sum(pairs: Vec<(u32, u32)>) -> u32 {
-    pairs.iter().fold(0, |acc, (num1, num2)| acc + (num1 * num2))
+fn do_sum(pairs: &[(u32, u32)]) -> u64 {
+    pairs.iter().map(|&(num1, num2)| num1 as u64 * num2 as u64).sum()
+}
+
+/*
+    Every number in a mul has to fit a u32, wherever it is, so that summing never has to fail.
+ */
+fn load_memory(input: &str) -> Result<Vec<String>, Error> {
+    for (idx, line) in input.lines().enumerate() {
+        for c in MULS_RE.captures_iter(line) {
+            for number in c.iter().skip(1).flatten() {
+                helpers::parse_number::<u32>(number.as_str(), idx + 1, number.start() + 1)?;
+            }
+        }
+    }
+    Ok(input.lines().map(String::from).collect())
+}
+
+pub struct Day03 {
+    lines: Vec<String>
+}
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Mull It Over";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Day03 { lines: load_memory(input)? })
+    }
+
+    fn part_one(&self) -> Result<Option<u64>, Error> {
+        Ok(Some(self.lines.iter()
+            .map(|line| do_sum(&find_pairs(line)))
+            .sum()))
+    }
+
+    fn part_two(&self) -> Result<Option<u64>, Error> {
+        // The do() and don't() state carries over between lines
+        let mega_value = self.lines.concat();
+        Ok(Some(do_sum(&find_pairs(&mega_value))))
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::helpers;
-
     use super::*;
 
     #[test]
     fn part_one_simple() {
         let pairs = find_pairs("xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))");
-        let sum = do_sum(pairs);
+        let sum = do_sum(&pairs);
         assert_eq!(161, sum);
     }
 
     #[test]
-    fn part_one() {
-        let mut pair_list: Vec<Vec<(u32, u32)>> = Vec::new();
-
-        if let Ok(lines) = helpers::read_lines("./src/resources/day03_input.txt") {
-            for line in lines.flatten() {
-                pair_list.push(find_pairs(&line));
-            }
-        }
-    
-        let sum = pair_list.iter()
-            .map(|pairs| do_sum(pairs.to_vec()))
-            .fold(0, |acc, value| acc + value);
+    fn test_part_one() {
+        let sum = Day03::load("./src/resources/day03_input.txt").unwrap().part_one().unwrap().unwrap();
         println!("{}", sum);
     }
 
@@ -90,26 +125,26 @@ mod tests {
     #[test]
     fn part_two_simple() {
         let pairs = find_pairs("xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))");
-        let sum = do_sum(pairs);
+        let sum = do_sum(&pairs);
         assert_eq!(48, sum);
     }
 
     #[test]
-    fn part_two() {
-        let mut mega_value = String::new();
-
-        if let Ok(lines) = helpers::read_lines("./src/resources/day03_input.txt") {
-            for line in lines.flatten() {
-                mega_value.push_str(&line);
-            }
+    fn test_number_too_large() {
+        assert_eq!(Some(6), Day03::parse("mul(2,3)").unwrap().part_one().unwrap());
+        assert_eq!(Some(10_000_000_000), Day03::parse("mul(100000,100000)").unwrap().part_one().unwrap());
+        match Day03::parse("mul(2,3)\nxmul(4294967296,2)") {
+            Err(Error::BadNumber { line, column, value }) => assert_eq!((2, 6, "4294967296"), (line, column, value.as_str())),
+            _ => panic!("expected a bad number")
         }
-    
-        let pairs = find_pairs(&mega_value);
-        println!("{}", pairs.len());
-        let sum = do_sum(pairs);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let sum = Day03::load("./src/resources/day03_input.txt").unwrap().part_two().unwrap().unwrap();
         println!("{}", sum);
-        // not 72700613, 10046858 (too low), 43983129
-        // answer is 100189366
+        // Rejected guesses are recorded in answers.txt
+        assert_eq!(100189366, sum);
     }
 
-}
\ No newline at end of file
+}
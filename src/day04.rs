@@ -1,47 +1,56 @@
-use crate::helpers;
-
-fn load_chars(file_name: &str) -> Vec<String> {
-    let mut return_value: Vec<String> = Vec::new();
-    if let Ok(lines) = helpers::read_lines("./src/resources/day03_input.txt") {
-        for line in lines.flatten() {
-            return_value.push(line);
-        }
-    
-    }
-    return_value
-}
+use crate::error::Error;
+use crate::grid::Grid;
+use crate::registry::Status;
+use crate::solution::Solution;
+use crate::word_search::{self, Pattern};
 
-fn test_forward(input: &Vec<String>, coord: (usize, usize)) -> bool {
-    let row = input.get(coord.0).unwrap();
-    (coord.1 + 3 < row.len())
-        && row.chars().nth(coord.1) == Some('X')
-        && row.chars().nth(coord.1 + 1) == Some('M')
-        && row.chars().nth(coord.1 + 2) == Some('A')
-        && row.chars().nth(coord.1 + 3) == Some('S')
-}
+const X_MAS: &str = "M.S
+.A.
+M.S";
 
-fn test_backward(input: &Vec<String>, coord: (usize, usize)) -> bool {
-    let row = input.get(coord.0).unwrap();
-    (coord.1 > 3 )
-        && row.chars().nth(coord.1) == Some('X')
-        && row.chars().nth(coord.1 - 1) == Some('M')
-        && row.chars().nth(coord.1 - 2) == Some('A')
-        && row.chars().nth(coord.1 - 3) == Some('S')
+pub struct Day04 {
+    grid: Grid<char>
 }
 
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Ceres Search";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Day04 { grid: Grid::parse(input)? })
+    }
+
+    fn part_one(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(word_search::find_word(&self.grid, "XMAS").len()))
+    }
+
+    fn part_two(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(word_search::find_pattern(&self.grid, &Pattern::stencil(X_MAS, '.')).len()))
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn check_forward() {
-        let mut my_vec = Vec::new();
-        my_vec.push("XMASASAMXFH".to_string());
-
-        assert_eq!(true, test_forward(&my_vec, (0, 0)));
-        assert_eq!(false, test_forward(&my_vec, (0, 3)));
-        assert_eq!(false, test_forward(&my_vec, (0, 9)));
-        assert_eq!(true, test_backward(&my_vec, (0, 7)));
+    fn check_x_mas() {
+        let day = Day04::parse("M.S\n.A.\nM.S\n").unwrap();
+        assert_eq!(Some(1), day.part_two().unwrap());
+        let day = Day04::parse("M.M\n.A.\nM.S\n").unwrap();
+        assert_eq!(Some(0), day.part_two().unwrap());
+    }
+
+    #[test]
+    fn part_one_simple() {
+        assert_eq!(Some(18), Day04::load("./src/resources/day04_simple.txt").unwrap().part_one().unwrap());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn part_two_simple() {
+        assert_eq!(Some(9), Day04::load("./src/resources/day04_simple.txt").unwrap().part_two().unwrap());
+    }
+}
@@ -1,133 +1,880 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::error::Error;
+use crate::graph;
 use crate::helpers;
-use once_cell::sync::Lazy;
-use regex::Regex;
+use crate::registry::Status;
+use crate::solution::Solution;
 
 /*
     PageRule.  Before must come vefore after
  */
-struct PageRule {
-    before_page_num: u32,
-    after_page_num: u32
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PageRule {
+    pub before_page_num: u32,
+    pub after_page_num: u32
+}
+
+/*
+    A rule an update breaks.  The positions are where the rule's pages are in the update,
+    so before_position is after after_position.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub rule: PageRule,
+    pub before_position: usize,
+    pub after_position: usize
 }
 
-fn load_input(input_file_name: &str) -> (Vec<PageRule>, Vec<Vec<u32>>) {
-    static RULES_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?<first>\d+)\|(?<second>\d+)").unwrap());
+/*
+    The rules indexed by page, so checking a pair of pages doesn't depend on how many rules there are
+ */
+#[derive(Debug, Default)]
+pub struct RuleSet {
+    pairs: HashSet<(u32, u32)>,
+    // For each page, the pages that must come before it and after it
+    befores: HashMap<u32, BTreeSet<u32>>,
+    afters: HashMap<u32, BTreeSet<u32>>
+}
+
+impl RuleSet {
+
+    pub fn insert(&mut self, rule: PageRule) {
+        if self.pairs.insert((rule.before_page_num, rule.after_page_num)) {
+            self.befores.entry(rule.after_page_num).or_default().insert(rule.before_page_num);
+            self.afters.entry(rule.before_page_num).or_default().insert(rule.after_page_num);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    pub fn contains(&self, before_page_num: u32, after_page_num: u32) -> bool {
+        self.pairs.contains(&(before_page_num, after_page_num))
+    }
+
+    /*
+        The pages a rule says must come before page, in page order
+     */
+    pub fn must_come_before(&self, page: u32) -> impl Iterator<Item = u32> + '_ {
+        self.befores.get(&page).into_iter().flatten().copied()
+    }
+
+    pub fn must_come_after(&self, page: u32) -> impl Iterator<Item = u32> + '_ {
+        self.afters.get(&page).into_iter().flatten().copied()
+    }
+
+    /*
+        Every rule, ordered by the page that comes first and then the page after it
+     */
+    pub fn rules(&self) -> impl Iterator<Item = PageRule> + '_ {
+        let mut pairs: Vec<(u32, u32)> = self.pairs.iter().copied().collect();
+        pairs.sort();
+        pairs.into_iter().map(|(before_page_num, after_page_num)| PageRule { before_page_num, after_page_num })
+    }
+
+    /*
+        How two pages compare under the rules, Equal when no rule relates them.  Only a consistent
+        comparator when the rules are a total order on the pages being sorted.
+     */
+    pub fn compare(&self, first: u32, second: u32) -> Ordering {
+        if self.contains(first, second) {
+            Ordering::Less
+        } else if self.contains(second, first) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /*
+        Pairs of different pages in the update that no rule orders, directly or through other
+        pages of the update, in update order.  Empty when the update's rules have a cycle.
+     */
+    pub fn unrelated_pairs(&self, pages: &[u32]) -> Vec<(u32, u32)> {
+        self.analyse_update(pages).unrelated
+    }
 
-    let mut rules: Vec<PageRule> = Vec::new();
+    /*
+        Whether the rules leave exactly one correct order for the update, as analyse reports it
+     */
+    pub fn is_total_order(&self, pages: &[u32]) -> bool {
+        self.analyse_update(pages).is_total_order()
+    }
+
+    /*
+        Whether there is a rule between every pair of different pages, which makes compare a total
+        order on the update as long as the rules have no cycle
+     */
+    fn relates_every_pair(&self, pages: &[u32]) -> bool {
+        pages.iter().enumerate().all(|(idx, &page)| pages[idx + 1..].iter()
+            .all(|&later| page == later || self.compare(page, later) != Ordering::Equal))
+    }
+
+    /*
+        Whether no rule puts a later page before an earlier one
+     */
+    pub fn is_ordered(&self, pages: &[u32]) -> bool {
+        pages.iter().enumerate()
+            .all(|(idx, &page)| pages[idx + 1..].iter().all(|&later| !self.contains(later, page)))
+    }
+
+    /*
+        Every rule the update breaks, in the order of the earlier page's position
+     */
+    pub fn violations(&self, pages: &[u32]) -> Vec<Violation> {
+        let mut return_value: Vec<Violation> = Vec::new();
+        for (after_position, &after_page_num) in pages.iter().enumerate() {
+            for (before_position, &before_page_num) in pages.iter().enumerate().skip(after_position + 1) {
+                if self.contains(before_page_num, after_page_num) {
+                    let rule = PageRule { before_page_num, after_page_num };
+                    return_value.push(Violation { rule, before_position, after_position });
+                }
+            }
+        }
+        return_value
+    }
+
+    /*
+        The rules as a Graphviz digraph, an edge from each page to the pages that must follow it.
+        Given an update, only its pages and the rules between them are drawn, labelled with their
+        positions, and the rules it breaks are drawn in red.
+     */
+    pub fn to_dot(&self, update: Option<&[u32]>) -> String {
+        let mut return_value = String::from("digraph rules {\n    rankdir=LR;\n");
+        let rules: Vec<PageRule> = match update {
+            Some(pages) => {
+                for (idx, page) in pages.iter().enumerate() {
+                    return_value.push_str(&format!("    {} [label=\"{} ({})\"];\n", page, page, idx + 1));
+                }
+                self.rules().filter(|rule| pages.contains(&rule.before_page_num) && pages.contains(&rule.after_page_num)).collect()
+            },
+            None => self.rules().collect()
+        };
+        let broken: HashSet<PageRule> = update.map_or(HashSet::new(), |pages| {
+            self.violations(pages).into_iter().map(|violation| violation.rule).collect()
+        });
+        for rule in rules {
+            let style = if broken.contains(&rule) { " [color=red, penwidth=2]" } else { "" };
+            return_value.push_str(&format!("    {} -> {}{};\n", rule.before_page_num, rule.after_page_num, style));
+        }
+        return_value.push_str("}\n");
+        return_value
+    }
+}
+
+impl FromIterator<PageRule> for RuleSet {
+    fn from_iter<I: IntoIterator<Item = PageRule>>(iter: I) -> RuleSet {
+        let mut return_value = RuleSet::default();
+        iter.into_iter().for_each(|rule| return_value.insert(rule));
+        return_value
+    }
+}
+
+fn load_input(input: &str) -> Result<(RuleSet, Vec<Vec<u32>>), Error> {
+    let mut rules = RuleSet::default();
     let mut pages: Vec<Vec<u32>> = Vec::new();
 
-    if let Ok(lines) = helpers::read_lines(input_file_name) {
-        for line in lines.flatten() {
-            // If there is a "|", this is a rule
-            // If there are commas, it will be a list of pages
-            if RULES_RE.is_match(&line) {
-                let capture = RULES_RE.captures(&line).unwrap();
-                
-                rules.push(PageRule{
-                    before_page_num: capture["first"].parse().unwrap(),
-                    after_page_num: capture["second"].parse().unwrap()
-                });
-            } else if !line.is_empty() {
-                let mut these_pages: Vec<u32> = Vec::new();
-                line.split(",").for_each(|strvalue| these_pages.push(strvalue.parse().unwrap())); 
-                pages.push(these_pages);
+    for (idx, line) in input.lines().enumerate() {
+        // If there is a "|", this is a rule
+        // If there are commas, it will be a list of pages
+        if line.contains('|') {
+            let numbers: Vec<u32> = helpers::parse_numbers(line, idx + 1, '|')?;
+            match numbers[..] {
+                [before_page_num, after_page_num] => rules.insert(PageRule { before_page_num, after_page_num }),
+                _ => return Err(Error::BadLine { line: idx + 1, content: line.to_string(), expected: "a rule like 47|53" })
+            }
+        } else if !line.trim().is_empty() {
+            let update: Vec<u32> = helpers::parse_numbers(line, idx + 1, ',')?;
+            if update.is_empty() {
+                return Err(Error::BadLine { line: idx + 1, content: line.to_string(), expected: "a list of pages like 75,47,61" });
             }
+            pages.push(update);
         }
     }
 
-    return (rules, pages)
+    Ok((rules, pages))
 }
 
-fn are_pages_ordered(pages: &Vec<u32>, rules: &Vec<PageRule>) -> bool {
-    rules.iter().map(|rule| test_pages(&pages, &rule)).all(|rule_matched| rule_matched)
+fn are_pages_ordered(pages: &[u32], rules: &RuleSet) -> bool {
+    rules.is_ordered(pages)
 }
 
-fn test_pages(pages: &Vec<u32>, rule: &PageRule) -> bool {
-    let before_pos = pages.iter().position(|&x| x == rule.before_page_num);
-    let after_pos = pages.iter().position(|&x| x == rule.after_page_num);
-    
-    match (before_pos, after_pos) {
-        (Some(before), Some(after)) => before < after,
-        _ => true
+/*
+    Fixes an update.  When there is a rule between every pair of its pages a plain sort does it;
+    otherwise the comparator isn't transitive and the topological sort is needed.  Rules on a cycle
+    can relate every pair too, and sort_by may panic on a comparator like that, so the cycle check
+    comes first.
+ */
+fn sort_update(pages: &[u32], rules: &RuleSet) -> Result<Vec<u32>, Error> {
+    let order = correct_order(pages, rules)?;
+    if rules.relates_every_pair(pages) {
+        let mut return_value = pages.to_vec();
+        return_value.sort_by(|&first, &second| rules.compare(first, second));
+        Ok(return_value)
+    } else {
+        Ok(order)
     }
 }
 
-fn middle_entry(pages: &Vec<u32>) -> u32 {
+fn middle_entry(pages: &[u32]) -> u32 {
     let middle_idx = pages.len() / 2;
     pages[middle_idx]
 }
 
-fn correct_order(pages: &Vec<u32>, rules: &Vec<PageRule>) -> Vec<u32> {
-    let mut return_value = pages.clone();
-    let mut rule_idx = 0;
-    while rule_idx < rules.len() {
-        let rule = &rules[rule_idx];
-        if test_pages(&return_value, rule) {
-            rule_idx += 1;
+/*
+    Puts the pages in an order that satisfies the rules with Kahn's algorithm, only looking at
+    rules between pages in the update.  When several pages could come next the one earliest in the
+    update goes first, so pages the rules don't constrain keep their order.
+ */
+fn correct_order(pages: &[u32], rules: &RuleSet) -> Result<Vec<u32>, Error> {
+    let order = topological_order(&rule_edges(pages, rules)).map_err(|cycle| cycle_error(pages, &cycle))?;
+    Ok(order.into_iter().map(|idx| pages[idx]).collect())
+}
+
+/*
+    Edges between positions in the update, so repeated pages are handled like any other
+ */
+fn rule_edges(pages: &[u32], rules: &RuleSet) -> Vec<Vec<usize>> {
+    let mut return_value: Vec<Vec<usize>> = vec![Vec::new(); pages.len()];
+    for (before, &before_page_num) in pages.iter().enumerate() {
+        for (after, &after_page_num) in pages.iter().enumerate() {
+            if rules.contains(before_page_num, after_page_num) {
+                return_value[before].push(after);
+            }
+        }
+    }
+    return_value
+}
+
+/*
+    Kahn's algorithm over positions, taking the earliest ready position first.  Fails with a cycle
+    when the edges don't allow any order.
+ */
+fn topological_order(successors: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let mut in_degree: Vec<usize> = vec![0; successors.len()];
+    successors.iter().flatten().for_each(|&after| in_degree[after] += 1);
+
+    let mut ready: BTreeSet<usize> = (0..successors.len()).filter(|&idx| in_degree[idx] == 0).collect();
+    let mut return_value: Vec<usize> = Vec::with_capacity(successors.len());
+    while let Some(idx) = ready.pop_first() {
+        return_value.push(idx);
+        for &next in &successors[idx] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.insert(next);
+            }
+        }
+    }
+
+    if return_value.len() < successors.len() {
+        return Err(find_cycle(successors, &in_degree));
+    }
+    Ok(return_value)
+}
+
+fn cycle_error(pages: &[u32], cycle: &[usize]) -> Error {
+    let cycle: Vec<String> = cycle.iter().map(|&idx| pages[idx].to_string()).collect();
+    Error::BadInput { expected: "rules without a cycle", found: format!("the cycle {}", cycle.join(" -> ")) }
+}
+
+/*
+    Once Kahn's algorithm stalls, every position left still has a predecessor left, so walking
+    back through them must come round to a position already seen.  Returns that cycle in rule order,
+    starting and ending with the same position.
+ */
+fn find_cycle(successors: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+    let remaining = |idx: usize| in_degree[idx] > 0;
+    let predecessor = |idx: usize| (0..successors.len())
+        .find(|&candidate| remaining(candidate) && successors[candidate].contains(&idx))
+        .expect("a stalled position has a predecessor");
+
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = (0..in_degree.len()).find(|&idx| remaining(idx)).expect("a stalled sort has positions left");
+    while !path.contains(&current) {
+        path.push(current);
+        current = predecessor(current);
+    }
+
+    let start = path.iter().position(|&idx| idx == current).unwrap();
+    let mut return_value: Vec<usize> = path[start..].to_vec();
+    return_value.push(current);
+    return_value.reverse();
+    return_value
+}
+
+/*
+    One step of a repair: the page is taken out at from and put back so that it ends up at to.
+    Positions count from 0 in the update as it is just before the move.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub page: u32,
+    pub from: usize,
+    pub to: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Repair {
+    // The pages left where they were, in update order
+    pub kept: Vec<u32>,
+    pub moves: Vec<Move>,
+    pub repaired: Vec<u32>
+}
+
+/*
+    The fewest single page moves that put the update in an order the rules allow.  The pages left
+    alone are the longest subsequence consistent with the rules, counting what the rules imply within
+    the update: with 3|2 and 2|1, the update 1,2,3 can only keep one page.
+ */
+pub fn minimum_repair(pages: &[u32], rules: &RuleSet) -> Result<Repair, Error> {
+    let count = pages.len();
+    let mut successors = rule_edges(pages, rules);
+    topological_order(&successors).map_err(|cycle| cycle_error(pages, &cycle))?;
+
+    // forced[i][j] when the rules put the page at i before the page at j, directly or through other pages
+    let forced = graph::transitive_closure(&successors);
+
+    // A page clashes with a later one the rules put before it.  Clashes are transitive, so the
+    // pages that can all stay are an antichain of that order.
+    let keep = largest_antichain(count, |earlier, later| earlier < later && forced[later][earlier]);
+
+    // Kept pages keep their relative order, the rest go wherever the rules want them
+    let kept: Vec<usize> = (0..count).filter(|&idx| keep[idx]).collect();
+    for pair in kept.windows(2) {
+        successors[pair[0]].push(pair[1]);
+    }
+    let target = topological_order(&successors).expect("kept pages are consistent with the rules");
+
+    // Moving each page to just after the page it follows in the target keeps every placed page in
+    // target order, so once all are placed the update is the target
+    let mut current: Vec<usize> = (0..count).collect();
+    let mut placed = keep.clone();
+    let mut moves: Vec<Move> = Vec::new();
+    for (target_idx, &idx) in target.iter().enumerate() {
+        if placed[idx] {
             continue;
         }
-        let current_before_idx = return_value.iter().position(|&x| x == rule.before_page_num);
-        let current_after_idx = return_value.iter().position(|&x| x == rule.after_page_num);
-        match (current_before_idx, current_after_idx) {
-            (Some(before), Some(after)) => {
-                return_value.remove(before);
-                return_value.insert(after, rule.before_page_num);
-                rule_idx = 0;
-                continue;
+        let from = current.iter().position(|&other| other == idx).unwrap();
+        current.remove(from);
+        let to = match target[..target_idx].iter().rev().find(|&&other| placed[other]) {
+            Some(&previous) => current.iter().position(|&other| other == previous).unwrap() + 1,
+            None => current.iter().position(|&other| placed[other]).unwrap_or(0)
+        };
+        current.insert(to, idx);
+        placed[idx] = true;
+        moves.push(Move { page: pages[idx], from, to });
+    }
+
+    Ok(Repair {
+        kept: kept.iter().map(|&idx| pages[idx]).collect(),
+        moves,
+        repaired: current.iter().map(|&idx| pages[idx]).collect()
+    })
+}
+
+/*
+    The largest set of elements no two of which are related by less, a strict partial order on 0..count.
+    By Dilworth and König it is what a maximum matching between "from" and "to" copies of the
+    elements leaves uncovered: elements whose from copy is reachable by alternating paths from an
+    unmatched from copy while their to copy is not.
+ */
+fn largest_antichain<F: Fn(usize, usize) -> bool>(count: usize, less: F) -> Vec<bool> {
+    fn augment<F: Fn(usize, usize) -> bool>(from: usize, less: &F, visited: &mut [bool], matched_to: &mut [Option<usize>]) -> bool {
+        for to in 0..matched_to.len() {
+            if less(from, to) && !visited[to] {
+                visited[to] = true;
+                if matched_to[to].is_none_or(|other| augment(other, less, visited, matched_to)) {
+                    matched_to[to] = Some(from);
+                    return true;
+                }
             }
-            _ => {
-                // nothing
+        }
+        false
+    }
+
+    // matched_to[to] is the from copy matched with the to copy
+    let mut matched_to: Vec<Option<usize>> = vec![None; count];
+    let matched_from: Vec<bool> = (0..count)
+        .map(|from| augment(from, &less, &mut vec![false; count], &mut matched_to))
+        .collect();
+
+    let mut reached_from: Vec<bool> = matched_from.iter().map(|&matched| !matched).collect();
+    let mut reached_to: Vec<bool> = vec![false; count];
+    let mut queue: Vec<usize> = (0..count).filter(|&from| reached_from[from]).collect();
+    while let Some(from) = queue.pop() {
+        for to in 0..count {
+            if less(from, to) && !reached_to[to] && matched_to[to] != Some(from) {
+                reached_to[to] = true;
+                if let Some(next) = matched_to[to] {
+                    if !reached_from[next] {
+                        reached_from[next] = true;
+                        queue.push(next);
+                    }
+                }
+            }
+        }
+    }
+
+    (0..count).map(|idx| reached_from[idx] && !reached_to[idx]).collect()
+}
+
+/*
+    The input's rule graph in DOT, optionally for just one update (counting from 1)
+ */
+pub fn rules_to_dot(input: &str, update: Option<usize>) -> Result<String, Error> {
+    let (rules, updates) = load_input(input)?;
+    match update {
+        Some(number) => {
+            let pages = number.checked_sub(1).and_then(|idx| updates.get(idx)).ok_or_else(|| Error::BadInput {
+                expected: "an update in the input",
+                found: format!("update {} of {}", number, updates.len())
+            })?;
+            Ok(rules.to_dot(Some(pages)))
+        },
+        None => Ok(rules.to_dot(None))
+    }
+}
+
+/*
+    How the rules behave within one update
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateAnalysis {
+    pub pages: Vec<u32>,
+    // A cycle among the update's rules, which leaves no valid order
+    pub cycle: Option<Vec<u32>>,
+    // Pairs that no rule orders, directly or through other pages, when there is no cycle
+    pub unrelated: Vec<(u32, u32)>,
+    pub rule_count: usize,
+    // Rules that follow from the update's other rules
+    pub redundant: Vec<PageRule>
+}
+
+impl UpdateAnalysis {
+
+    pub fn is_total_order(&self) -> bool {
+        self.cycle.is_none() && self.unrelated.is_empty()
+    }
+}
+
+/*
+    The structure of the whole rule graph, and of the rules within each update
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Analysis {
+    pub page_count: usize,
+    pub rule_count: usize,
+    // Groups of pages that each have a cycle through every page in the group
+    pub cyclic_components: Vec<Vec<u32>>,
+    pub cycles: Vec<Vec<u32>>,
+    // Whether the cycle search stopped before finding them all
+    pub more_cycles: bool,
+    // Rules that the other rules imply, each on its own, so dropping several at once can lose
+    // information.  Inside a cyclic component every rule is implied by the rest of the cycle,
+    // so only rules leading out of one are considered.
+    pub redundant: Vec<PageRule>,
+    pub updates: Vec<UpdateAnalysis>
+}
+
+impl Analysis {
+
+    pub fn report(&self) -> Vec<String> {
+        let join = |pages: &[u32], separator: &str| pages.iter().map(|page| page.to_string()).collect::<Vec<String>>().join(separator);
+        let rule_names = |rules: &[PageRule]| rules.iter()
+            .map(|rule| format!("{}|{}", rule.before_page_num, rule.after_page_num))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let mut return_value: Vec<String> = vec![format!("{} rules between {} pages", self.rule_count, self.page_count)];
+        if self.cyclic_components.is_empty() {
+            return_value.push("No cycles, the rules order every page consistently".to_string());
+        } else {
+            return_value.push(format!("{} group{} of pages on cycles:",
+                self.cyclic_components.len(), if self.cyclic_components.len() == 1 { "" } else { "s" }));
+            for component in &self.cyclic_components {
+                return_value.push(format!("    {} pages: {}", component.len(), join(component, ", ")));
+            }
+            return_value.push(format!("Cycles{}:", if self.more_cycles { " (the first few, there are more)" } else { "" }));
+            for cycle in &self.cycles {
+                return_value.push(format!("    {}", join(cycle, " -> ")));
+            }
+        }
+        return_value.push(format!("{} redundant rules outside cycles{}{}",
+            self.redundant.len(), if self.redundant.is_empty() { "" } else { ": " }, rule_names(&self.redundant)));
+
+        for (idx, update) in self.updates.iter().enumerate() {
+            let summary = match &update.cycle {
+                Some(cycle) => format!("rules cycle {}", join(cycle, " -> ")),
+                None if update.unrelated.is_empty() => format!("total order, {} of {} rules implied by the others",
+                    update.redundant.len(), update.rule_count),
+                None => {
+                    let pairs: Vec<String> = update.unrelated.iter().map(|(first, second)| format!("{}/{}", first, second)).collect();
+                    format!("not a total order, nothing orders {}", pairs.join(", "))
+                }
+            };
+            return_value.push(format!("Update {} ({}): {}", idx + 1, join(&update.pages, ","), summary));
+        }
+        let total = self.updates.iter().filter(|update| update.is_total_order()).count();
+        return_value.push(format!("{} of {} updates have a total order", total, self.updates.len()));
+        return_value
+    }
+}
+
+impl RuleSet {
+
+    pub fn analyse(&self, updates: &[Vec<u32>], max_cycles: usize) -> Analysis {
+        let pages: Vec<u32> = self.pairs.iter().flat_map(|&(before, after)| [before, after]).collect::<BTreeSet<u32>>().into_iter().collect();
+        let index: HashMap<u32, usize> = pages.iter().enumerate().map(|(idx, &page)| (page, idx)).collect();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); pages.len()];
+        for rule in self.rules() {
+            successors[index[&rule.before_page_num]].push(index[&rule.after_page_num]);
+        }
+
+        let mut component_of: Vec<usize> = vec![0; pages.len()];
+        let mut cyclic_components: Vec<Vec<u32>> = Vec::new();
+        for (idx, component) in graph::strongly_connected_components(&successors).into_iter().enumerate() {
+            component.iter().for_each(|&node| component_of[node] = idx);
+            if component.len() > 1 || successors[component[0]].contains(&component[0]) {
+                cyclic_components.push(component.iter().map(|&node| pages[node]).collect());
+            }
+        }
+        cyclic_components.sort();
+
+        let (cycles, more_cycles) = graph::elementary_cycles(&successors, max_cycles);
+        let cycles = cycles.iter().map(|cycle| cycle.iter().map(|&node| pages[node]).collect()).collect();
+
+        // A rule is redundant when its after page can still be reached without it
+        let redundant = self.rules()
+            .filter(|rule| {
+                let (from, to) = (index[&rule.before_page_num], index[&rule.after_page_num]);
+                component_of[from] != component_of[to] && reachable_without_edge(&successors, from, to)
+            })
+            .collect();
+
+        Analysis {
+            page_count: pages.len(),
+            rule_count: self.len(),
+            cyclic_components,
+            cycles,
+            more_cycles,
+            redundant,
+            updates: updates.iter().map(|pages| self.analyse_update(pages)).collect()
+        }
+    }
+
+    fn analyse_update(&self, pages: &[u32]) -> UpdateAnalysis {
+        let successors = rule_edges(pages, self);
+        let rule_count = successors.iter().map(|afters| afters.len()).sum();
+        let rule_at = |before: usize, after: usize| PageRule { before_page_num: pages[before], after_page_num: pages[after] };
+        let (cycle, unrelated, redundant) = match topological_order(&successors) {
+            Err(cycle) => (Some(cycle.iter().map(|&idx| pages[idx]).collect()), Vec::new(), Vec::new()),
+            Ok(_) => {
+                let forced = graph::transitive_closure(&successors);
+                let mut redundant: Vec<PageRule> = Vec::new();
+                for (before, afters) in successors.iter().enumerate() {
+                    for &after in afters {
+                        if (0..pages.len()).any(|via| forced[before][via] && forced[via][after]) {
+                            redundant.push(rule_at(before, after));
+                        }
+                    }
+                }
+                redundant.sort_by_key(|rule| (rule.before_page_num, rule.after_page_num));
+                (None, unforced_pairs(pages, &forced), redundant)
+            }
+        };
+        UpdateAnalysis { pages: pages.to_vec(), cycle, unrelated, rule_count, redundant }
+    }
+}
+
+fn unforced_pairs(pages: &[u32], forced: &[Vec<bool>]) -> Vec<(u32, u32)> {
+    let mut return_value: Vec<(u32, u32)> = Vec::new();
+    for (idx, &page) in pages.iter().enumerate() {
+        for (later_idx, &later) in pages.iter().enumerate().skip(idx + 1) {
+            if page != later && !forced[idx][later_idx] && !forced[later_idx][idx] {
+                return_value.push((page, later));
             }
         }
-        rule_idx += 1;
     }
     return_value
 }
 
+fn reachable_without_edge(successors: &[Vec<usize>], from: usize, to: usize) -> bool {
+    let mut seen: Vec<bool> = vec![false; successors.len()];
+    let mut queue: Vec<usize> = successors[from].iter().copied().filter(|&next| next != to).collect();
+    while let Some(node) = queue.pop() {
+        if node == to {
+            return true;
+        }
+        if !seen[node] {
+            seen[node] = true;
+            queue.extend(successors[node].iter().copied());
+        }
+    }
+    false
+}
+
+/*
+    Analyses the input's rules without requiring them to be consistent, unlike solving it
+ */
+pub fn analyse(input: &str, max_cycles: usize) -> Result<Analysis, Error> {
+    let (rules, updates) = load_input(input)?;
+    Ok(rules.analyse(&updates, max_cycles))
+}
+
+pub struct Day05 {
+    rules: RuleSet,
+    updates: Vec<Vec<u32>>
+}
+
+impl Day05 {
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+}
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Print Queue";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        let (rules, updates) = load_input(input)?;
+        Ok(Day05 { rules, updates })
+    }
+
+    fn part_one(&self) -> Result<Option<u32>, Error> {
+        Ok(Some(self.updates.iter()
+            .filter(|pages| are_pages_ordered(pages, &self.rules))
+            .map(|pages| middle_entry(pages))
+            .sum()))
+    }
+
+    // Fails when rules contradict each other within an update that needs reordering
+    fn part_two(&self) -> Result<Option<u32>, Error> {
+        let mut sum = 0;
+        for pages in self.updates.iter().filter(|pages| !are_pages_ordered(pages, &self.rules)) {
+            sum += middle_entry(&sort_update(pages, &self.rules)?);
+        }
+        Ok(Some(sum))
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        let mut return_value: Vec<String> = Vec::new();
+        // Only updates part two reorders count towards the answer with an order the rules don't fix
+        for (idx, pages) in self.updates.iter().enumerate().filter(|(_, pages)| !are_pages_ordered(pages, &self.rules)) {
+            let unrelated = self.rules.unrelated_pairs(pages);
+            if !unrelated.is_empty() {
+                let pairs: Vec<String> = unrelated.iter().map(|(first, second)| format!("{}/{}", first, second)).collect();
+                return_value.push(format!("update {} has no rule, direct or implied, between pages {}, so its middle page may be ambiguous",
+                    idx + 1, pairs.join(", ")));
+            }
+        }
+        return_value
+    }
+
+    fn explain(&self) -> Vec<String> {
+        let mut return_value: Vec<String> = Vec::new();
+        for (idx, pages) in self.updates.iter().enumerate() {
+            let listed: Vec<String> = pages.iter().map(|page| page.to_string()).collect();
+            let violations = self.rules.violations(pages);
+            if violations.is_empty() {
+                return_value.push(format!("Update {} ({}): ordered", idx + 1, listed.join(",")));
+                continue;
+            }
+            return_value.push(format!("Update {} ({}): {} rule{} broken",
+                idx + 1, listed.join(","), violations.len(), if violations.len() == 1 { "" } else { "s" }));
+            for violation in violations {
+                let rule = violation.rule;
+                return_value.push(format!("    {}|{}: {} is at position {}, {} at position {}",
+                    rule.before_page_num, rule.after_page_num,
+                    rule.before_page_num, violation.before_position + 1,
+                    rule.after_page_num, violation.after_position + 1));
+            }
+            if let Ok(repair) = minimum_repair(pages, &self.rules) {
+                let moves: Vec<String> = repair.moves.iter()
+                    .map(|step| format!("{} from position {} to {}", step.page, step.from + 1, step.to + 1))
+                    .collect();
+                return_value.push(format!("    fixed by moving {}", moves.join(", then ")));
+            }
+        }
+        return_value
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_part_two() {
-        let (rules, all_pages) = load_input("./src/resources/day05_input.txt");
-        let entry_sum: u32 = all_pages.iter()
-            .filter(|pages| !are_pages_ordered(pages, &rules))
-            .map(|pages| correct_order(pages, &rules))
-            .map(|pages| middle_entry(&pages))
-            .sum();
-        assert_eq!(4598, entry_sum);
+        assert_eq!(4598, Day05::load("./src/resources/day05_input.txt").unwrap().part_two().unwrap().unwrap());
     }
 
     #[test]
     fn test_correct_order() {
-        let (rules, all_pages) = load_input("./src/resources/day05_simple.txt");
-        assert_eq!(vec![97,75,47,61,53], correct_order(&all_pages[3], &rules));
-        assert_eq!(vec![61,29,13], correct_order(&all_pages[4], &rules));
-        assert_eq!(vec![97,75,47,29,13], correct_order(&all_pages[5], &rules));
+        let (rules, all_pages) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
+        assert_eq!(vec![97,75,47,61,53], correct_order(&all_pages[3], &rules).unwrap());
+        assert_eq!(vec![61,29,13], correct_order(&all_pages[4], &rules).unwrap());
+        assert_eq!(vec![97,75,47,29,13], correct_order(&all_pages[5], &rules).unwrap());
+
+        assert_eq!(123, Day05::load("./src/resources/day05_simple.txt").unwrap().part_two().unwrap().unwrap());
+
+    }
+
+    #[test]
+    fn test_correct_order_cycle() {
+        let (rules, all_pages) = load_input("1|2\n2|3\n3|1\n4|1\n\n4,3,2,1\n").unwrap();
+        let error = correct_order(&all_pages[0], &rules).unwrap_err();
+        assert_eq!("Expected rules without a cycle, found the cycle 3 -> 1 -> 2 -> 3", error.to_string());
+        // Part one doesn't need the cyclic update sorted, part two reports the cycle
+        let day = Day05::parse("1|2\n2|1\n\n1,2\n3,4\n").unwrap();
+        assert_eq!(Some(4), day.part_one().unwrap());
+        assert!(matches!(day.part_two(), Err(Error::BadInput { .. })));
+    }
+
+    #[test]
+    fn test_sort_update() {
+        let (rules, all_pages) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
+        assert!(all_pages.iter().all(|pages| rules.is_total_order(pages)));
+        assert_eq!(Ordering::Less, rules.compare(47, 53));
+        assert_eq!(Ordering::Greater, rules.compare(53, 47));
+        assert_eq!(vec![97,75,47,61,53], sort_update(&all_pages[3], &rules).unwrap());
+        assert_eq!(vec![97,75,47,29,13], sort_update(&all_pages[5], &rules).unwrap());
+
+        // 1 and 3 are unrelated, so both 1,2,3 and 3,1,2 would satisfy the rules
+        let day = Day05::parse("1|2\n3|2\n\n2,1,3\n").unwrap();
+        assert_eq!(vec![(1, 3)], day.rules.unrelated_pairs(&day.updates[0]));
+        assert_eq!(vec![1, 3, 2], sort_update(&day.updates[0], &day.rules).unwrap());
+        assert_eq!(1, day.warnings().len());
+        assert!(Day05::load("./src/resources/day05_simple.txt").unwrap().warnings().is_empty());
+
+        // 1 before 3 follows from 1|2 and 2|3, so neither update is ambiguous
+        let day = Day05::parse("1|2\n2|3\n\n3,2,1\n1,2,3\n").unwrap();
+        assert!(day.updates.iter().all(|pages| day.rules.is_total_order(pages)));
+        assert!(day.rules.unrelated_pairs(&day.updates[0]).is_empty());
+        assert_eq!(vec![1, 2, 3], sort_update(&day.updates[0], &day.rules).unwrap());
+        assert!(day.warnings().is_empty());
+        assert_eq!(Some(2), day.part_two().unwrap());
+
+        // Every pair has a rule but they go round in a cycle, so compare isn't an order; a slice
+        // this long is sorted with more than insertion sort, which panics on such a comparator
+        let rules: String = (0..61).flat_map(|page| (1..=30).map(move |step| format!("{}|{}\n", page + 10, (page + step) % 61 + 10))).collect();
+        let update = "67,70,52,19,12,15,28,54,35,32,65,36,29,20,13,21,59,39,25,69,63,68,50,33,56,45,31,22,49,47,44,\
+            42,43,53,30,55,24,27,60,10,48,37,57,11,62,16,23,34,51,40,38,66,41,17,26,14,58,61,64,46,18";
+        let day = Day05::parse(&format!("{}\n{}\n", rules, update)).unwrap();
+        assert!(day.rules.relates_every_pair(&day.updates[0]));
+        assert!(matches!(day.part_two(), Err(Error::BadInput { .. })));
+
+        // An ambiguous update that is already in order isn't reordered, so it doesn't warn
+        let day = Day05::parse("1|2\n3|2\n\n1,3,2\n").unwrap();
+        assert!(!day.rules.is_total_order(&day.updates[0]));
+        assert!(day.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_violations() {
+        let (rules, all_pages) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
+        assert!(rules.violations(&all_pages[0]).is_empty());
+        let rule = PageRule { before_page_num: 97, after_page_num: 75 };
+        assert_eq!(vec![Violation { rule, before_position: 1, after_position: 0 }], rules.violations(&all_pages[3]));
+        assert_eq!(4, rules.violations(&all_pages[5]).len());
+
+        let explanation = Day05::load("./src/resources/day05_simple.txt").unwrap().explain();
+        assert_eq!("Update 1 (75,47,61,53,29): ordered", explanation[0]);
+        assert_eq!(vec!["Update 4 (75,97,47,61,53): 1 rule broken", "    97|75: 97 is at position 2, 75 at position 1",
+            "    fixed by moving 75 from position 1 to 2"], explanation[3..6]);
+    }
+
+    #[test]
+    fn test_minimum_repair() {
+        let (rules, all_pages) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
+        assert!(minimum_repair(&all_pages[0], &rules).unwrap().moves.is_empty());
+        let repair = minimum_repair(&all_pages[3], &rules).unwrap();
+        assert_eq!(vec![Move { page: 75, from: 0, to: 1 }], repair.moves);
+        assert_eq!(vec![97,75,47,61,53], repair.repaired);
+        let repair = minimum_repair(&all_pages[5], &rules).unwrap();
+        assert_eq!(vec![97, 75, 47], repair.kept);
+        assert_eq!(2, repair.moves.len());
+        assert!(rules.is_ordered(&repair.repaired));
+
+        // Keeping 1 and 2, or 2 and 3, works but not all three
+        let (rules, all_pages) = load_input("3|1\n\n1,2,3\n").unwrap();
+        assert_eq!(1, minimum_repair(&all_pages[0], &rules).unwrap().moves.len());
+        // 3|2 and 2|1 together mean no two pages can stay
+        let (rules, all_pages) = load_input("3|2\n2|1\n\n1,2,3\n").unwrap();
+        let repair = minimum_repair(&all_pages[0], &rules).unwrap();
+        assert_eq!(2, repair.moves.len());
+        assert_eq!(vec![3, 2, 1], repair.repaired);
+    }
 
-        let entry_sum: u32 = all_pages.iter()
-            .filter(|pages| !are_pages_ordered(pages, &rules))
-            .map(|pages| correct_order(pages, &rules))
-            .map(|pages| middle_entry(&pages))
-            .sum();
-        assert_eq!(123, entry_sum);
+    #[test]
+    fn test_to_dot() {
+        let input = "47|53\n97|75\n97|47\n75|47\n\n75,97,47\n";
+        let full = rules_to_dot(input, None).unwrap();
+        assert_eq!("digraph rules {\n    rankdir=LR;\n    47 -> 53;\n    75 -> 47;\n    97 -> 47;\n    97 -> 75;\n}\n", full);
+        let update = rules_to_dot(input, Some(1)).unwrap();
+        assert!(update.contains("    97 [label=\"97 (2)\"];\n"));
+        assert!(update.contains("    97 -> 75 [color=red, penwidth=2];\n"));
+        assert!(update.contains("    97 -> 47;\n"));
+        assert!(!update.contains("53"));
+        assert!(rules_to_dot(input, Some(2)).is_err());
+        assert!(rules_to_dot(input, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_analyse() {
+        let analysis = analyse(include_str!("resources/day05_simple.txt"), 10).unwrap();
+        assert_eq!(7, analysis.page_count);
+        assert!(analysis.cyclic_components.is_empty());
+        // The sample's rules are a total order, so every rule not between neighbours is implied
+        assert_eq!(21 - 6, analysis.redundant.len());
+        assert!(analysis.updates.iter().all(|update| update.is_total_order()));
+        assert_eq!(10, analysis.updates[0].rule_count);
+        assert_eq!(6, analysis.updates[0].redundant.len());
 
+        let analysis = analyse("1|2\n2|3\n3|1\n3|4\n1|4\n\n1,2,3\n1,4\n2,4\n", 10).unwrap();
+        assert_eq!(vec![vec![1, 2, 3]], analysis.cyclic_components);
+        assert_eq!(vec![vec![1, 2, 3, 1]], analysis.cycles);
+        // Either rule into 4 follows from the other and the cycle
+        assert_eq!(2, analysis.redundant.len());
+        assert_eq!(Some(vec![1, 2, 3, 1]), analysis.updates[0].cycle);
+        assert!(analysis.updates[1].is_total_order());
+        assert_eq!(vec![(2, 4)], analysis.updates[2].unrelated);
+        let report = analysis.report();
+        assert_eq!("Update 3 (2,4): not a total order, nothing orders 2/4", report[report.len() - 2]);
+        assert_eq!("1 of 3 updates have a total order", report[report.len() - 1]);
+        // run's warnings and the report judge updates the same way
+        let (rules, updates) = load_input("1|2\n2|3\n3|1\n3|4\n1|4\n\n1,2,3\n1,4\n2,4\n").unwrap();
+        for (pages, update) in updates.iter().zip(&analysis.updates) {
+            assert_eq!(update.is_total_order(), rules.is_total_order(pages));
+            assert_eq!(update.unrelated, rules.unrelated_pairs(pages));
+        }
+
+        // 1 before 3 is implied, so the update is a total order with nothing unrelated
+        let analysis = analyse("1|2\n2|3\n\n3,2,1\n", 10).unwrap();
+        assert!(analysis.updates[0].unrelated.is_empty());
+        assert!(analysis.updates[0].is_total_order());
+        assert_eq!("1 of 1 updates have a total order", analysis.report().last().unwrap());
     }
 
     #[test]
     fn test_part_one() {
-        let (rules, all_pages) = load_input("./src/resources/day05_input.txt");
-        let entry_sum: u32 = all_pages.iter()
-            .filter(|pages| are_pages_ordered(pages, &rules))
-            .map(|pages| middle_entry(pages))
-            .sum();
-        assert_eq!(5452, entry_sum);
+        assert_eq!(5452, Day05::load("./src/resources/day05_input.txt").unwrap().part_one().unwrap().unwrap());
     }
 
     #[test]
     fn test_simple() {
-        let (rules, all_pages) = load_input("./src/resources/day05_simple.txt");
+        let (rules, all_pages) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
         assert!(are_pages_ordered(&all_pages[0], &rules));
         assert_eq!(61, middle_entry(&all_pages[0]));
         assert!(are_pages_ordered(&all_pages[1], &rules));
@@ -138,20 +885,36 @@ mod tests {
         assert!(!are_pages_ordered(&all_pages[4], &rules));
         assert!(!are_pages_ordered(&all_pages[5], &rules));
 
-        let entry_sum: u32 = all_pages.iter()
-            .filter(|pages| are_pages_ordered(pages, &rules))
-            .map(|pages| middle_entry(pages))
-            .sum();
-        assert_eq!(143, entry_sum);
+        assert_eq!(143, Day05::load("./src/resources/day05_simple.txt").unwrap().part_one().unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert!(matches!(load_input("1|2|3\n"), Err(Error::BadLine { line: 1, .. })));
+        assert!(matches!(load_input("1|2\n\n,\n"), Err(Error::BadLine { line: 3, .. })));
+        assert!(matches!(load_input("1|2\n\n1,2\n,,\n"), Err(Error::BadLine { line: 4, .. })));
     }
 
     #[test]
     fn test_test_pages() {
         let pages = vec![13, 44, 23, 67];
-        assert!(test_pages(&pages, &PageRule{ before_page_num: 44, after_page_num: 23}));
-        assert!(test_pages(&pages, &PageRule{ before_page_num: 13, after_page_num: 23}));
-        assert!(test_pages(&pages, &PageRule{ before_page_num: 23, after_page_num: 67}));
-        assert!(!test_pages(&pages, &PageRule{ before_page_num: 23, after_page_num: 44}));
-        assert!(test_pages(&pages, &PageRule{ before_page_num: 23, after_page_num: 99}));
+        let single = |before_page_num, after_page_num| RuleSet::from_iter([PageRule { before_page_num, after_page_num }]);
+        assert!(single(44, 23).is_ordered(&pages));
+        assert!(single(13, 23).is_ordered(&pages));
+        assert!(single(23, 67).is_ordered(&pages));
+        assert!(!single(23, 44).is_ordered(&pages));
+        assert!(single(23, 99).is_ordered(&pages));
+    }
+
+    #[test]
+    fn test_rule_set() {
+        let (rules, _) = load_input(include_str!("resources/day05_simple.txt")).unwrap();
+        assert_eq!(21, rules.len());
+        assert!(rules.contains(47, 53));
+        assert!(!rules.contains(53, 47));
+        assert_eq!(vec![75, 97], rules.must_come_before(47).collect::<Vec<_>>());
+        assert_eq!(vec![13, 29, 53, 61], rules.must_come_after(47).collect::<Vec<_>>());
+        assert_eq!(0, rules.must_come_before(97).count());
+        assert_eq!(PageRule { before_page_num: 29, after_page_num: 13 }, rules.rules().next().unwrap());
     }
 }
\ No newline at end of file
@@ -1,248 +1,456 @@
-use std::{collections::HashSet, hash::{Hash, Hasher}};
+use std::collections::{BTreeSet, HashSet};
 
-use crate::helpers;
+use crate::error::Error;
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+use crate::parallel;
+use crate::registry::Status;
+use crate::simulation::{self, Outcome};
+use crate::solution::Solution;
 
-fn load_input(input_file_name: &str) -> Vec<String> {
-    if let Ok(lines) = helpers::read_lines(input_file_name) {
-        lines.flatten().collect()
-    } else{
-        Vec::new()
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Pose {
+    position: Point,
+    heading: Direction
+}
+
+impl Pose {
+
+    fn turn_ninety_degrees(&mut self) {
+        self.heading = self.heading.turn_right();
+    }
+
+    fn next_candidate(&self) -> Pose {
+        Pose { position: self.position.step(self.heading), heading: self.heading }
     }
+
 }
 
-#[derive(Debug)]
-#[derive(Copy)]
-#[derive(Clone)]
-enum Heading {
-    Up,
-    Down,
-    Left,
-    Right
+/*
+    For every free cell and heading, the cell the guard stops on before the next obstruction,
+    or None if it walks off the map.  Lets the guard jump straight from one turn to the next.
+ */
+struct JumpTable {
+    stops: Grid<[Option<Point>; 4]>
 }
 
-#[derive(Copy)]
-#[derive(Clone)]
-struct Pose {
-    x: isize,
-    y: isize,
-    heading: Heading
+impl JumpTable {
+
+    fn build(grid: &Grid<char>) -> JumpTable {
+        let mut stops: Grid<[Option<Point>; 4]> = Grid::filled(grid.width(), grid.height(), [None; 4]);
+        let positions: Vec<Point> = grid.positions().collect();
+        for (idx, heading) in Direction::ORTHOGONAL.into_iter().enumerate() {
+            // Visit cells so the one ahead of each has already been filled in
+            let ordered: Box<dyn Iterator<Item = &Point>> = match heading {
+                Direction::Up | Direction::Left => Box::new(positions.iter()),
+                _ => Box::new(positions.iter().rev())
+            };
+            for &position in ordered {
+                let ahead = position.step(heading);
+                stops[position][idx] = match grid.get(ahead) {
+                    None => None,
+                    Some('#') => Some(position),
+                    Some(_) => stops[ahead][idx]
+                };
+            }
+        }
+        JumpTable { stops }
+    }
+
+    /*
+        Where the guard stops from pose.  An extra obstruction doesn't change the table; it only
+        matters when it is ahead of the guard and nearer than the stop the table gives.
+     */
+    fn stop(&self, pose: &Pose, extra_obstruction: Option<Point>) -> Option<Point> {
+        let stop = self.stops.get(pose.position)?[heading_index(pose.heading)];
+        let extra_obstruction = match extra_obstruction {
+            Some(extra_obstruction) => extra_obstruction,
+            None => return stop
+        };
+        // Distance along the heading, and whether a point is in line with the guard at all
+        let unit = pose.heading.unit();
+        let distance = |point: Point| (point.x - pose.position.x) * unit.x + (point.y - pose.position.y) * unit.y;
+        let in_line = |point: Point| (point.x - pose.position.x) * unit.y == (point.y - pose.position.y) * unit.x;
+        let ahead = in_line(extra_obstruction) && distance(extra_obstruction) > 0;
+        let nearer = stop.is_none_or(|stop| distance(extra_obstruction) <= distance(stop));
+        if ahead && nearer {
+            Some(extra_obstruction - unit)
+        } else {
+            stop
+        }
+    }
 }
 
-impl Pose {
+fn heading_index(heading: Direction) -> usize {
+    Direction::ORTHOGONAL.iter().position(|&direction| direction == heading).expect("the guard only moves orthogonally")
+}
+
+/*
+    The lab and every guard in it.  Guards walk independently: a guard's starting cell is
+    not an obstruction to the others.
+ */
+struct Map {
+    grid: Grid<char>,
+    jumps: JumpTable,
+    guards: Vec<Pose>
+}
+
+impl Map {
 
-    fn same_location(&self, other: &Pose) -> bool {
-        self.x == other.x && self.y == other.y
+    fn count_distinct_positions(&self) -> usize {
+        self.visited_positions().len()
     }
 
-    fn turn_ninety_degrees(&mut self) {
-        self.heading = match self.heading {
-            Heading::Up => Heading::Right,
-            Heading::Right => Heading::Down,
-            Heading::Down => Heading::Left,
-            Heading::Left => Heading::Up
+    fn visited_positions(&self) -> HashSet<Point> {
+        self.guards.iter()
+            .flat_map(|&guard| self.walk(guard))
+            .map(|pose| pose.position)
+            .collect()
+    }
+
+    /*
+        The guard's next pose, one cell or one turn on, or None once it walks off the map
+     */
+    fn step(&self, pose: &Pose, extra_obstruction: Option<Point>) -> Option<Pose> {
+        let next_position = pose.next_candidate();
+        match self.grid.get(next_position.position)? {
+            _ if Some(next_position.position) == extra_obstruction => {
+                let mut turned = *pose;
+                turned.turn_ninety_degrees();
+                Some(turned)
+            },
+            '#' => {
+                let mut turned = *pose;
+                turned.turn_ninety_degrees();
+                Some(turned)
+            },
+            _ => Some(next_position)
         }
     }
 
-    fn next_candidate(&self) -> Pose {
-        match self.heading {
-            Heading::Up => Pose{x: self.x - 1, y: self.y, heading: Heading::Up},
-            Heading::Left => Pose{x: self.x, y: self.y - 1, heading: Heading::Left},
-            Heading::Down => Pose{x: self.x + 1, y: self.y, heading: Heading::Down},
-            Heading::Right => Pose{x: self.x, y: self.y + 1, heading: Heading::Right}
+    /*
+        Every pose of the guard's walk in order.  If the walk loops it stops at the first repeated pose.
+     */
+    fn walk(&self, start: Pose) -> Vec<Pose> {
+        let mut return_value = vec![start];
+        simulation::detect_cycle(start, |pose| {
+            let next = self.step(pose, None);
+            return_value.extend(next);
+            next
+        });
+        return_value
+    }
+
+    /*
+        Records the guard's walk a step at a time, optionally with an extra obstruction.
+        Unlike the loop search this doesn't jump between turns, so the two can be checked against each other.
+     */
+    fn trace(&self, guard: usize, extra_obstruction: Option<Point>) -> Vec<TraceStep> {
+        let start = self.guards[guard];
+        let record = |step: usize, pose: Pose, event: TraceEvent| TraceStep {
+            guard, step, position: pose.position, heading: pose.heading, event
+        };
+        let mut return_value = vec![record(0, start, TraceEvent::Start)];
+        let outcome = simulation::detect_cycle(start, |pose| {
+            let next = self.step(pose, extra_obstruction);
+            let step = return_value.len();
+            return_value.push(match next {
+                Some(next) if next.position == pose.position => record(step, next, TraceEvent::Turn),
+                Some(next) => record(step, next, TraceEvent::Move),
+                None => record(step, pose.next_candidate(), TraceEvent::Exit)
+            });
+            next
+        });
+        // The last pose recorded is the first one to repeat
+        if let (Outcome::Cycle(_), Some(last)) = (outcome, return_value.last_mut()) {
+            last.event = TraceEvent::Loop;
         }
+        return_value
     }
 
-}
+    /*
+        The guard's pose after its next turn, or None once it walks off the map
+     */
+    fn jump(&self, pose: &Pose, extra_obstruction: Option<Point>) -> Option<Pose> {
+        let mut turned = Pose { position: self.jumps.stop(pose, extra_obstruction)?, heading: pose.heading };
+        turned.turn_ninety_degrees();
+        Some(turned)
+    }
+
+    /*
+        Walks the guard with one extra obstruction.  The walk is a loop as soon as a position is
+        revisited with the same heading, since everything after that repeats.  Only the poses at
+        turns are needed for that, so the guard jumps from turn to turn.
+     */
+    fn ends_in_loop(&self, start: Pose, extra_obstruction: Point) -> bool {
+        let outcome = simulation::detect_cycle(start, |pose| self.jump(pose, Some(extra_obstruction)));
+        matches!(outcome, Outcome::Cycle(_))
+    }
 
-impl PartialEq for Pose {
+    fn traps_a_guard(&self, extra_obstruction: Point) -> bool {
+        self.guards.iter().any(|&guard| self.ends_in_loop(guard, extra_obstruction))
+    }
 
-    fn eq(&self, other: &Self) -> bool {
-        self.x == other.x && self.y == other.y
+    /*
+        An obstruction only changes a walk if it is on the original path, so those are the only
+        candidates.  The guards' starting positions are excluded because they are occupied.
+        Each candidate is its own walk, so they are checked in parallel.
+     */
+    fn looping_obstructions(&self) -> BTreeSet<Point> {
+        let candidates: Vec<Point> = self.obstruction_candidates().into_iter().collect();
+        let loops = parallel::map(&candidates, |&candidate| self.traps_a_guard(candidate));
+        candidates.into_iter().zip(loops)
+            .filter(|&(_, ends_in_loop)| ends_in_loop)
+            .map(|(candidate, _)| candidate)
+            .collect()
     }
-}
 
-impl Eq for Pose {
+    fn obstruction_candidates(&self) -> BTreeSet<Point> {
+        self.visited_positions().into_iter()
+            .filter(|&candidate| self.guards.iter().all(|guard| guard.position != candidate))
+            .collect()
+    }
 
 }
 
-impl Hash for Pose {
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_OBSTRUCTION: &str = "\x1b[90m";
+const ANSI_PATH: &str = "\x1b[33m";
+const ANSI_GUARD: &str = "\x1b[1;32m";
+
+fn heading_char(heading: Direction) -> char {
+    match heading {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        _ => '<'
+    }
+}
 
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.x.hash(state);
-        self.y.hash(state);
+fn char_heading(c: char) -> Option<Direction> {
+    match c {
+        '^' => Some(Direction::Up),
+        '>' => Some(Direction::Right),
+        'v' => Some(Direction::Down),
+        '<' => Some(Direction::Left),
+        _ => None
     }
 }
 
-struct Map {
-    rows: Vec<String>,
-    current_pose: Pose
+/*
+    The guards' walks, for drawing the map the way the puzzle text does: '|' and '-' where a guard
+    moved vertically or horizontally, '+' where it did both, and each start shown by the guard's symbol.
+ */
+pub struct GuardWalk {
+    map: Map,
+    walks: Vec<Vec<Pose>>
 }
 
-impl Map {
+impl GuardWalk {
 
-    fn position_on_map(&self, pose: &Pose) -> bool {
-        if pose.x < 0 || pose.y < 0 {
-            return false;
-        }
+    pub fn parse(input: &str) -> Result<GuardWalk, Error> {
+        let map = load_map_with_guards(input)?;
+        let walks = map.guards.iter().map(|&guard| map.walk(guard)).collect();
+        Ok(GuardWalk { map, walks })
+    }
 
-        pose.x < self.rows.len() as isize
-            && pose.y < self.rows[self.current_pose.x as usize].len() as isize
-    }
-
-    fn current_position_on_map(&self) -> bool {
-        self.position_on_map(&self.current_pose)
-    }
-
-    fn do_navigate<F: FnMut(Pose, Pose), G: FnMut(Pose), H: FnMut(Pose, Pose)>(&mut self, mut replace_fn: F, mut obstruct_fn: G, mut navigate_fn: H) {
-        let original_pose = self.current_pose;
-        while self.current_position_on_map() {
-            let mut replace_current = true;
-            let next_position = self.current_pose.next_candidate();
-            if self.position_on_map(&next_position) {
-                // The target is still on the map.  Check to see if there is an obstruction
-                match self.rows[next_position.x as usize].chars().nth(next_position.y as usize).unwrap() {
-                    '#' => {
-                        // There is an obstruction.  Move ninety degrees
-                        self.current_pose.turn_ninety_degrees();
-                        replace_current = false;
-                        obstruct_fn(next_position);
-                    },
-                    _ => {
-                        // No obstruction, move into the next cell
-                        navigate_fn(self.current_pose, next_position)
-                    }
-                }
+    /*
+        The number of frames in the longest walk, one per pose including turns
+     */
+    pub fn len(&self) -> usize {
+        self.walks.iter().map(|walk| walk.len()).max().unwrap_or(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /*
+        The whole walk, once the guards have gone
+     */
+    pub fn render(&self, colour: bool) -> String {
+        let walks: Vec<&[Pose]> = self.walks.iter().map(|walk| walk.as_slice()).collect();
+        self.draw(&walks, &[], colour)
+    }
+
+    /*
+        The walk so far, with each guard still on the map drawn where it is after the given frame
+     */
+    pub fn render_frame(&self, frame: usize, colour: bool) -> String {
+        let walks: Vec<&[Pose]> = self.walks.iter().map(|walk| &walk[..walk.len().min(frame + 1)]).collect();
+        let guards: Vec<Pose> = self.walks.iter().filter_map(|walk| walk.get(frame).copied()).collect();
+        self.draw(&walks, &guards, colour)
+    }
+
+    fn draw(&self, walks: &[&[Pose]], guards: &[Pose], colour: bool) -> String {
+        let grid = &self.map.grid;
+        // (vertical, horizontal) movement through each cell
+        let mut moves: Grid<(bool, bool)> = Grid::filled(grid.width(), grid.height(), (false, false));
+        for pair in walks.iter().flat_map(|walk| walk.windows(2)) {
+            if pair[0].position == pair[1].position {
+                continue;
             }
-            if replace_current {
-                replace_fn(self.current_pose, next_position);
-                self.current_pose = next_position;
+            let vertical = matches!(pair[0].heading, Direction::Up | Direction::Down);
+            for position in [pair[0].position, pair[1].position] {
+                if let Some(cell) = moves.get_mut(position) {
+                    if vertical { cell.0 = true } else { cell.1 = true }
+                }
             }
         }
-        self.current_pose = original_pose;
-    }
-
-    fn count_distinct_positions(&mut self) -> usize {
-        let mut visited_cells: Vec<Pose> = Vec::new();
-        self.do_navigate(
-            |current, next| visited_cells.push(current),
-            |obstruction| {},
-            |current, next| {}
-        );
-        visited_cells.into_iter().collect::<HashSet<Pose>>().into_iter().len()
-    }
-
-    fn looping_obstacle_candidates(&mut self) -> usize {
-        let mut encountered_obstructions: Vec<Pose> = Vec::new();
-        // Navigate the path, finding obstructions
-        self.do_navigate(
-            |current, next| {},
-            |obstruction| encountered_obstructions.push(obstruction),
-            |current, next| {}
-        );
-        // Now that we have obstructions, find obstruction candidates that would cause a loop
-        let mut looping_obstruction_candidates: Vec<(Pose, Pose)> = Vec::new();
-        self.do_navigate(
-            |current, next| {},
-            |obstruction| {},
-            |current, next| {
-                match current.heading {
-                    Heading::Up => {
-                        // Look to the right.  See if we have encountered any obstructions already
-                        match encountered_obstructions.iter().find(|obs| obs.x == current.x && obs.y > current.y) {
-                            Some(p) => looping_obstruction_candidates.push((current, next)),
-                            _ => { 
-                                // nothing 
-                            }
-                        };
-                    },
-                    Heading::Down => {
-                        // Look to the right.  See if we have encountered any obstructions already
-                        match encountered_obstructions.iter().find(|obs| obs.x == current.x && obs.y < current.y) {
-                            Some(p) => looping_obstruction_candidates.push((current, next)),
-                            _ => { 
-                                // nothing 
-                            }
-                        };
-                    },
-                    Heading::Left => {
-                        match encountered_obstructions.iter().find(|obs| obs.x < current.x && obs.y == current.y) {
-                            Some(p) => looping_obstruction_candidates.push((current, next)),
-                            _ => { 
-                                // nothing 
-                            }
-                        };
-                    },
-                    Heading::Right => {
-                        match encountered_obstructions.iter().find(|obs| obs.x > current.x && obs.y == current.y) {
-                            Some(p) => looping_obstruction_candidates.push((current, next)),
-                            _ => { 
-                                // nothing 
-                            }
-                        };
-                    },
+
+        let starts = &self.map.guards;
+        let mut return_value = String::new();
+        for row in 0..grid.height() {
+            for col in 0..grid.width() {
+                let position = Point::new(col as isize, row as isize);
+                let guard = guards.iter().chain(starts.iter()).find(|guard| guard.position == position);
+                let (c, ansi) = match (guard, moves[position]) {
+                    (Some(guard), _) => (heading_char(guard.heading), ANSI_GUARD),
+                    (_, (true, true)) => ('+', ANSI_PATH),
+                    (_, (true, false)) => ('|', ANSI_PATH),
+                    (_, (false, true)) => ('-', ANSI_PATH),
+                    _ if grid[position] == '#' => ('#', ANSI_OBSTRUCTION),
+                    _ => ('.', "")
+                };
+                if colour && !ansi.is_empty() {
+                    return_value.push_str(ansi);
+                    return_value.push(c);
+                    return_value.push_str(ANSI_RESET);
+                } else {
+                    return_value.push(c);
                 }
             }
-        );
-
-        let mut new_obstructions: Vec<Pose> = Vec::new();
-
-        // for each candidate, update the map with the new obstacle and see if a loop occurs
-        for candidate_pair in looping_obstruction_candidates {
-            let mut new_rows = self.rows.clone();
-            let mut row_to_change = new_rows[candidate_pair.1.x as usize].clone();
-            row_to_change.replace_range(candidate_pair.1.y as usize..candidate_pair.1.y as usize +1, "#");
-
-            let mut new_map = Map {rows: new_rows, current_pose: self.current_pose };
-            let mut in_a_loop = false;
-            
-            let mut counter = 0;
-            new_map.do_navigate(
-                |_, _| {},
-                |_| {},
-                |current, next| {
-                    // listen for whether the path naviates through the point twice
-                    if current == candidate_pair.0 {
-                        counter += 1;
-                    }
-                });
-
-            if counter > 1 {
-                new_obstructions.push(candidate_pair.1);
-            }
+            return_value.push('\n');
         }
-        
-        new_obstructions.into_iter().collect::<HashSet<Pose>>().into_iter().len()
+        return_value
     }
+}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceEvent {
+    Start,
+    Move,
+    Turn,
+    // Walked off the map; the position is the cell it stepped into
+    Exit,
+    // Reached a pose it has already been in
+    Loop
 }
 
-fn locate_pos_and_heading(lines: &Vec<String>) -> Pose {
-    let mut row: usize = 0;
-    let mut col: usize = 0;
-
-    while row < lines.len() {
-        let line = &lines[row];
-        let position_option = line.chars().position(|c| c == '^');
-        match position_option {
-            Some(idx) => {
-                col = idx;
-                break;
-            },
-            _ => row += 1
-            
+impl TraceEvent {
+
+    fn name(self) -> &'static str {
+        match self {
+            TraceEvent::Start => "start",
+            TraceEvent::Move => "move",
+            TraceEvent::Turn => "turn",
+            TraceEvent::Exit => "exit",
+            TraceEvent::Loop => "loop"
         }
     }
+}
+
+fn heading_name(heading: Direction) -> &'static str {
+    match heading {
+        Direction::Up => "up",
+        Direction::Right => "right",
+        Direction::Down => "down",
+        _ => "left"
+    }
+}
+
+/*
+    One step of a guard's walk, the pose it is in after the step and what happened
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceStep {
+    pub guard: usize,
+    pub step: usize,
+    pub position: Point,
+    pub heading: Direction,
+    pub event: TraceEvent
+}
+
+impl TraceStep {
+
+    pub const CSV_HEADER: &'static str = "guard,step,x,y,heading,event";
+
+    pub fn to_json(&self) -> String {
+        format!("{{\"guard\":{},\"step\":{},\"x\":{},\"y\":{},\"heading\":\"{}\",\"event\":\"{}\"}}",
+            self.guard, self.step, self.position.x, self.position.y, heading_name(self.heading), self.event.name())
+    }
+
+    pub fn to_csv(&self) -> String {
+        format!("{},{},{},{},{},{}",
+            self.guard, self.step, self.position.x, self.position.y, heading_name(self.heading), self.event.name())
+    }
+}
 
-    Pose{x: row as isize, y: col as isize, heading: Heading::Up}
+/*
+    Every guard's walk, one after the other, with an optional extra obstruction
+ */
+pub fn trace(input: &str, extra_obstruction: Option<Point>) -> Result<Vec<TraceStep>, Error> {
+    let map = load_map_with_guards(input)?;
+    Ok((0..map.guards.len()).flat_map(|guard| map.trace(guard, extra_obstruction)).collect())
 }
 
+/*
+    Every guard on the map, marked by the way it is facing
+ */
+fn locate_guards(grid: &Grid<char>) -> Vec<Pose> {
+    grid.iter()
+        .filter_map(|(position, &c)| char_heading(c).map(|heading| Pose { position, heading }))
+        .collect()
+}
+
+fn load_map_with_guards(input: &str) -> Result<Map, Error> {
+    let grid = Grid::parse(input)?;
+    let guards = locate_guards(&grid);
+    if guards.is_empty() {
+        return Err(Error::BadInput { expected: "a guard (^, >, v or <)", found: "none".to_string() });
+    }
+    Ok(Map {
+        jumps: JumpTable::build(&grid),
+        grid,
+        guards
+    })
+}
 
-fn load_map(file_name: &str) -> Map {
-    let lines = load_input(file_name);
-    let starting_position = locate_pos_and_heading(&lines);
-    Map {
-        rows: lines,
-        current_pose: starting_position
+/*
+    The puzzle's lab has exactly one guard
+ */
+fn load_map(input: &str) -> Result<Map, Error> {
+    let map = load_map_with_guards(input)?;
+    if map.guards.len() > 1 {
+        let positions: Vec<String> = map.guards.iter()
+            .map(|guard| format!("({}, {})", guard.position.x, guard.position.y))
+            .collect();
+        return Err(Error::BadInput { expected: "one guard", found: format!("guards at {}", positions.join(", ")) });
+    }
+    Ok(map)
+}
+
+pub struct Day06 {
+    map: Map
+}
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Guard Gallivant";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Day06 { map: load_map(input)? })
+    }
+
+    fn part_one(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(self.map.count_distinct_positions()))
+    }
+
+    fn part_two(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(self.map.looping_obstructions().len()))
     }
 }
 
@@ -252,37 +460,116 @@ mod tests {
 
     #[test]
     fn test_simple() {
-        let mut map = load_map("./src/resources/day06_simple.txt");
-        assert_eq!(41, map.count_distinct_positions());
+        assert_eq!(41, Day06::load("./src/resources/day06_simple.txt").unwrap().part_one().unwrap().unwrap());
     }
 
     #[test]
     fn test_part_one() {
-        let mut map = load_map("./src/resources/day06_input.txt");
-        assert_eq!(5162, map.count_distinct_positions());
+        assert_eq!(5162, Day06::load("./src/resources/day06_input.txt").unwrap().part_one().unwrap().unwrap());
     }
 
     #[test]
     fn test_pose_next_candidate() {
-        let my_pose = Pose{x: 1, y: 3, heading: Heading::Up};
+        let my_pose = Pose{position: Point::new(3, 1), heading: Direction::Up};
         let next_pose = my_pose.next_candidate();
-        assert!(!my_pose.same_location(&next_pose));
-        assert_eq!(0, next_pose.x);
+        assert_ne!(my_pose.position, next_pose.position);
+        assert_eq!(0, next_pose.position.y);
     }
 
     #[test]
     fn test_part_two_simple() {
-        let mut map = load_map("./src/resources/day06_simple.txt");
-        assert_eq!(6, map.looping_obstacle_candidates());
-        // let candidates = map.looping_obstacle_candidates();
-    }
-
-    // #[test]
-    // fn test_part_two() {
-    //     let mut map = load_map("./src/resources/day06_input.txt");
-    //     println!("{}", map.count_looping_obstacles());
-    //     // 2092 fails ... too high
-    //     // assert_eq!(6, map.count_looping_obstacles());
-    // }
+        let map = load_map(include_str!("resources/day06_simple.txt")).unwrap();
+        let expected: BTreeSet<Point> = [(3, 6), (6, 7), (7, 7), (1, 8), (3, 8), (7, 9)].into_iter()
+            .map(|(x, y)| Point::new(x, y))
+            .collect();
+        assert_eq!(expected, map.looping_obstructions());
+    }
+
+    #[test]
+    fn test_loop_detection() {
+        let map = load_map(include_str!("resources/day06_simple.txt")).unwrap();
+        let guard = map.guards[0];
+        assert!(map.ends_in_loop(guard, Point::new(3, 6)));
+        assert!(!map.ends_in_loop(guard, Point::new(0, 0)));
+    }
+
+    #[test]
+    fn test_walk_that_loops() {
+        let day = Day06::parse(".#..\n...#\n#...\n.^#.\n").unwrap();
+        assert_eq!(Some(5), day.part_one().unwrap());
+        assert!(day.part_two().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_jump_table() {
+        let map = load_map(include_str!("resources/day06_simple.txt")).unwrap();
+        let start = map.guards[0];
+        assert_eq!(Some(Point::new(4, 1)), map.jumps.stop(&start, None));
+        assert_eq!(Some(Point::new(4, 4)), map.jumps.stop(&start, Some(Point::new(4, 3))));
+        assert_eq!(Some(Point::new(4, 1)), map.jumps.stop(&start, Some(Point::new(4, 8))));
+        assert_eq!(Some(Point::new(4, 1)), map.jumps.stop(&start, Some(Point::new(5, 3))));
+        let left = Pose { position: Point::new(3, 5), heading: Direction::Left };
+        assert_eq!(None, map.jumps.stop(&left, None));
+        assert_eq!(Some(Point::new(1, 5)), map.jumps.stop(&left, Some(Point::new(0, 5))));
+    }
+
+    #[test]
+    fn test_guards() {
+        let map = load_map("...\n.<.\n...\n").unwrap();
+        assert_eq!(vec![Pose { position: Point::new(1, 1), heading: Direction::Left }], map.guards);
+        assert_eq!(2, map.count_distinct_positions());
+        assert!(matches!(load_map("...\n.#.\n"), Err(Error::BadInput { .. })));
+        assert_eq!("Expected one guard, found guards at (0, 0), (2, 1)", load_map("v..\n..^\n").err().unwrap().to_string());
+
+        // Two guards walking out of opposite sides of the map
+        let map = load_map_with_guards("v..\n..^\n").unwrap();
+        assert_eq!(4, map.count_distinct_positions());
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = include_str!("resources/day06_simple.txt");
+        let steps = trace(input, None).unwrap();
+        assert_eq!("0,0,4,6,up,start", steps[0].to_csv());
+        assert_eq!("{\"guard\":0,\"step\":1,\"x\":4,\"y\":5,\"heading\":\"up\",\"event\":\"move\"}", steps[1].to_json());
+        assert_eq!("0,6,4,1,right,turn", steps[6].to_csv());
+        assert_eq!("0,55,7,10,down,exit", steps.last().unwrap().to_csv());
+        let distinct: HashSet<Point> = steps.iter().filter(|step| step.event != TraceEvent::Exit).map(|step| step.position).collect();
+        assert_eq!(41, distinct.len());
+
+        let steps = trace(input, Some(Point::new(3, 6))).unwrap();
+        assert_eq!(TraceEvent::Loop, steps.last().unwrap().event);
+        assert_eq!(1, steps.iter().filter(|step| step.event == TraceEvent::Loop).count());
+    }
+
+    #[test]
+    fn test_render() {
+        let walk = GuardWalk::parse(include_str!("resources/day06_simple.txt")).unwrap();
+        let expected = "\
+....#.....
+....+---+#
+....|...|.
+..#.|...|.
+..+-+-+#|.
+..|.|.|.|.
+.#+-^-+-+.
+.+----++#.
+#+----+|..
+......#|..
+";
+        assert_eq!(expected, walk.render(false));
+        let frame = "....#.....\n.........#\n..........\n..#.......\n....^..#..\n....|.....\n.#..^.....\n........#.\n#.........\n......#...\n";
+        assert_eq!(frame, walk.render_frame(2, false));
+        assert!(walk.render(true).contains("\x1b[33m+\x1b[0m"));
+    }
+
+    #[test]
+    fn test_parallel_matches_sequential() {
+        let map = load_map(include_str!("resources/day06_simple.txt")).unwrap();
+        let sequential: BTreeSet<Point> = map.obstruction_candidates().into_iter()
+            .filter(|&candidate| map.traps_a_guard(candidate))
+            .collect();
+        assert_eq!(sequential, map.looping_obstructions());
+    }
 
 }
\ No newline at end of file
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+use crate::error::Error;
+use crate::helpers;
+use crate::registry::Status;
+use crate::solution::Solution;
+
 fn blink_transform_map(input: &HashMap<u64, usize>) -> HashMap<u64, usize> {
     let mut return_value = HashMap::with_capacity(input.len());
 
@@ -24,11 +29,51 @@ fn blink_transform_map(input: &HashMap<u64, usize>) -> HashMap<u64, usize> {
 fn load_value_map(input: Vec<u64>) -> HashMap<u64, usize> {
     let mut return_value = HashMap::with_capacity(input.len());
     for value in input {
-        return_value.insert(value,1);
+        *return_value.entry(value).or_default() += 1;
     }
     return_value
 }
 
+fn load_stones(input: &str) -> Result<Vec<u64>, Error> {
+    let mut return_value: Vec<u64> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        return_value.extend(helpers::parse_numbers::<u64>(line, idx + 1, ' ')?);
+    }
+    Ok(return_value)
+}
+
+fn count_after_blinks(stones: &[u64], blinks: usize) -> usize {
+    let mut stones = load_value_map(stones.to_vec());
+    for _ in 0..blinks {
+        stones = blink_transform_map(&stones);
+    }
+    stones.values().sum()
+}
+
+pub struct Day11 {
+    stones: Vec<u64>
+}
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Plutonian Pebbles";
+    const STATUS: [Status; 2] = [Status::Complete, Status::Complete];
+
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self, Error> {
+        Ok(Day11 { stones: load_stones(input)? })
+    }
+
+    fn part_one(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(count_after_blinks(&self.stones, 25)))
+    }
+
+    fn part_two(&self) -> Result<Option<usize>, Error> {
+        Ok(Some(count_after_blinks(&self.stones, 75)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,7 +81,7 @@ mod tests {
     #[test]
     fn check_sample_6_blinks() {
         let mut stones = load_value_map(vec![125, 17]);
-        for x in 0..6 {
+        for _ in 0..6 {
             stones = blink_transform_map(&stones);
         }
         let total_stones: usize = stones.values().sum();
@@ -46,31 +91,31 @@ mod tests {
     #[test]
     fn check_sample_25_blinks() {
         let mut stones = load_value_map(vec![125, 17]);
-        for x in 0..25 {
+        for _ in 0..25 {
             stones = blink_transform_map(&stones);
         }
         let total_stones: usize = stones.values().sum();
         assert_eq!(total_stones, 55312);
     }
 
+    #[test]
+    fn check_sample_parse() {
+        assert_eq!(Some(55312), Day11::parse("125 17").unwrap().part_one().unwrap());
+    }
+
+    #[test]
+    fn check_duplicate_stones() {
+        assert_eq!(Some(2 * 19025), Day11::parse("125 125").unwrap().part_one().unwrap());
+    }
+
     #[test]
     fn check_part_one_25_blinks() {
-        let mut stones = load_value_map(vec![7725, 185, 2, 132869, 0, 1840437, 62, 26310]);
-        for x in 0..25 {
-            stones = blink_transform_map(&stones);
-        }
-        let total_stones: usize = stones.values().sum();
-        assert_eq!(total_stones, 233050);
+        assert_eq!(Day11::load("./src/resources/day11_input.txt").unwrap().part_one().unwrap().unwrap(), 233050);
     }
 
     #[test]
     fn check_part_two_75_blinks() {
-        let mut stones = load_value_map(vec![7725, 185, 2, 132869, 0, 1840437, 62, 26310]);
-        for x in 0..75 {
-            stones = blink_transform_map(&stones);
-        }
-        let total_stones: usize = stones.values().sum();
-        assert_eq!(total_stones, 276661131175807);
+        assert_eq!(Day11::load("./src/resources/day11_input.txt").unwrap().part_two().unwrap().unwrap(), 276661131175807);
     }
 
 }
\ No newline at end of file
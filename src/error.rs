@@ -0,0 +1,33 @@
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum Error {
+    MissingFile { path: String, source: io::Error },
+    Read { path: String, source: io::Error },
+    BadLine { line: usize, content: String, expected: &'static str },
+    BadNumber { line: usize, column: usize, value: String },
+    // Input that parses but doesn't describe a valid puzzle
+    BadInput { expected: &'static str, found: String }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::MissingFile { path, source } => write!(f, "Unable to open '{}': {}", path, source),
+            Error::Read { path, source } => write!(f, "Unable to read '{}': {}", path, source),
+            Error::BadLine { line, content, expected } => write!(f, "Line {}: expected {}, found '{}'", line, expected, content),
+            Error::BadNumber { line, column, value } => write!(f, "Line {}, column {}: '{}' is not a valid number", line, column, value),
+            Error::BadInput { expected, found } => write!(f, "Expected {}, found {}", expected, found)
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::MissingFile { source, .. } | Error::Read { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
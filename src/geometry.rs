@@ -0,0 +1,187 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/*
+    A point on a grid.  x is the column and y is the row, so y grows downwards like the puzzle text.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize
+}
+
+impl Point {
+
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn step(self, direction: Direction) -> Point {
+        self + direction.unit()
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl AddAssign for Point {
+
+    fn add_assign(&mut self, other: Point) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl SubAssign for Point {
+
+    fn sub_assign(&mut self, other: Point) {
+        *self = *self - other;
+    }
+}
+
+impl Mul<isize> for Point {
+    type Output = Point;
+
+    fn mul(self, factor: isize) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/*
+    Compass directions, declared clockwise starting from Up
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft
+}
+
+impl Direction {
+
+    pub const ORTHOGONAL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft
+    ];
+
+    pub fn unit(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::DownRight => Point::new(1, 1),
+            Direction::Down => Point::new(0, 1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::UpLeft => Point::new(-1, -1)
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Direction::ORTHOGONAL.contains(&self)
+    }
+
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+
+    /*
+        Turns 45 degrees clockwise
+     */
+    pub fn clockwise(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /*
+        Turns 45 degrees counter clockwise
+     */
+    pub fn counter_clockwise(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_arithmetic() {
+        let point = Point::new(3, -2);
+        assert_eq!(Point::new(4, 0), point + Point::new(1, 2));
+        assert_eq!(Point::new(2, -4), point - Point::new(1, 2));
+        assert_eq!(Point::new(9, -6), point * 3);
+        assert_eq!(Point::new(-3, 2), -point);
+        assert_eq!(7, point.manhattan_distance(Point::new(-1, 1)));
+        assert_eq!(Point::new(3, -3), point.step(Direction::Up));
+    }
+
+    #[test]
+    fn test_turns() {
+        assert_eq!(Direction::Right, Direction::Up.turn_right());
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+        assert_eq!(Direction::UpLeft, Direction::DownRight.reverse());
+        assert_eq!(Direction::UpRight, Direction::Up.clockwise());
+        assert_eq!(Direction::UpLeft, Direction::Up.counter_clockwise());
+        assert!(Direction::DownLeft.is_diagonal());
+        assert!(!Direction::Down.is_diagonal());
+    }
+
+    #[test]
+    fn test_units_cancel_out() {
+        for direction in Direction::ALL {
+            assert_eq!(Point::ORIGIN, direction.unit() + direction.reverse().unit());
+            assert_eq!(direction, direction.turn_right().turn_right().turn_right().turn_right());
+        }
+    }
+}
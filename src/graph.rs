@@ -0,0 +1,227 @@
+/*
+    Algorithms on directed graphs given as adjacency lists: successors[node] lists the nodes
+    it has an edge to, with nodes numbered from 0.
+ */
+
+/*
+    reachable[from][to] when there is a path of one or more edges from from to to
+ */
+pub fn transitive_closure(successors: &[Vec<usize>]) -> Vec<Vec<bool>> {
+    let count = successors.len();
+    let mut return_value: Vec<Vec<bool>> = vec![vec![false; count]; count];
+    for (from, tos) in successors.iter().enumerate() {
+        tos.iter().for_each(|&to| return_value[from][to] = true);
+    }
+    for via in 0..count {
+        for from in 0..count {
+            if return_value[from][via] {
+                let reachable = return_value[via].clone();
+                return_value[from].iter_mut().zip(reachable).for_each(|(cell, reachable)| *cell |= reachable);
+            }
+        }
+    }
+    return_value
+}
+
+/*
+    Tarjan's algorithm.  Each component is sorted, and components come out in reverse topological
+    order: nothing in a component has an edge to a component after it.
+ */
+pub fn strongly_connected_components(successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        successors: &'a [Vec<usize>],
+        index: Vec<Option<usize>>,
+        low_link: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next_index: usize,
+        components: Vec<Vec<usize>>
+    }
+
+    impl Tarjan<'_> {
+        fn visit(&mut self, node: usize) {
+            self.index[node] = Some(self.next_index);
+            self.low_link[node] = self.next_index;
+            self.next_index += 1;
+            self.stack.push(node);
+            self.on_stack[node] = true;
+
+            for &next in &self.successors[node] {
+                match self.index[next] {
+                    None => {
+                        self.visit(next);
+                        self.low_link[node] = self.low_link[node].min(self.low_link[next]);
+                    },
+                    Some(next_index) if self.on_stack[next] => self.low_link[node] = self.low_link[node].min(next_index),
+                    Some(_) => {}
+                }
+            }
+
+            // A node that can't reach anything earlier on the stack is the root of a component
+            if Some(self.low_link[node]) == self.index[node] {
+                let mut component: Vec<usize> = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                component.sort();
+                self.components.push(component);
+            }
+        }
+    }
+
+    let count = successors.len();
+    let mut tarjan = Tarjan {
+        successors,
+        index: vec![None; count],
+        low_link: vec![0; count],
+        on_stack: vec![false; count],
+        stack: Vec::new(),
+        next_index: 0,
+        components: Vec::new()
+    };
+    for node in 0..count {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    tarjan.components
+}
+
+/*
+    Elementary cycles, each starting and ending at its smallest node, found in order of that node.
+    A dense graph has exponentially many, so the search stops after limit cycles; the flag says
+    whether there were more.  This is Johnson's algorithm, whose blocking keeps the search from
+    exploring the same dead ends again, so the time between cycles stays small.
+ */
+pub fn elementary_cycles(successors: &[Vec<usize>], limit: usize) -> (Vec<Vec<usize>>, bool) {
+    struct Johnson<'a> {
+        successors: &'a [Vec<usize>],
+        start: usize,
+        blocked: Vec<bool>,
+        // blocked_by[node] are the nodes to unblock once node is unblocked
+        blocked_by: Vec<Vec<usize>>,
+        path: Vec<usize>,
+        cycles: Vec<Vec<usize>>,
+        limit: usize,
+        truncated: bool
+    }
+
+    impl Johnson<'_> {
+        fn unblock(&mut self, node: usize) {
+            self.blocked[node] = false;
+            for other in std::mem::take(&mut self.blocked_by[node]) {
+                if self.blocked[other] {
+                    self.unblock(other);
+                }
+            }
+        }
+
+        // Whether a cycle back to start was found through node
+        fn circuit(&mut self, node: usize) -> bool {
+            let mut found = false;
+            self.path.push(node);
+            self.blocked[node] = true;
+            for &next in &self.successors[node] {
+                if self.truncated || next < self.start {
+                    continue;
+                }
+                if next == self.start {
+                    if self.cycles.len() == self.limit {
+                        self.truncated = true;
+                        continue;
+                    }
+                    let mut cycle = self.path.clone();
+                    cycle.push(self.start);
+                    self.cycles.push(cycle);
+                    found = true;
+                } else if !self.blocked[next] && self.circuit(next) {
+                    found = true;
+                }
+            }
+            if found {
+                self.unblock(node);
+            } else {
+                for &next in &self.successors[node] {
+                    if next >= self.start && !self.blocked_by[next].contains(&node) {
+                        self.blocked_by[next].push(node);
+                    }
+                }
+            }
+            self.path.pop();
+            found
+        }
+    }
+
+    // Only nodes in the same component can be on a cycle together
+    let mut component_of: Vec<usize> = vec![0; successors.len()];
+    for (idx, component) in strongly_connected_components(successors).iter().enumerate() {
+        component.iter().for_each(|&node| component_of[node] = idx);
+    }
+    let within: Vec<Vec<usize>> = successors.iter().enumerate()
+        .map(|(node, nexts)| nexts.iter().copied().filter(|&next| component_of[next] == component_of[node]).collect())
+        .collect();
+
+    let mut johnson = Johnson {
+        successors: &within,
+        start: 0,
+        blocked: Vec::new(),
+        blocked_by: Vec::new(),
+        path: Vec::new(),
+        cycles: Vec::new(),
+        limit,
+        truncated: false
+    };
+    for start in 0..successors.len() {
+        johnson.start = start;
+        johnson.blocked = vec![false; successors.len()];
+        johnson.blocked_by = vec![Vec::new(); successors.len()];
+        johnson.circuit(start);
+        if johnson.truncated {
+            break;
+        }
+    }
+    (johnson.cycles, johnson.truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 0 and 2 -> 3 -> 4 -> 3
+    fn sample() -> Vec<Vec<usize>> {
+        vec![vec![1], vec![2], vec![0, 3], vec![4], vec![3]]
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let reachable = transitive_closure(&sample());
+        assert!(reachable[0][4]);
+        assert!(reachable[0][0]);
+        assert!(!reachable[3][0]);
+        assert!(reachable[4][4]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        assert_eq!(vec![vec![3, 4], vec![0, 1, 2]], strongly_connected_components(&sample()));
+        assert_eq!(vec![vec![1], vec![0]], strongly_connected_components(&[vec![1], vec![]]));
+    }
+
+    #[test]
+    fn test_elementary_cycles() {
+        assert_eq!((vec![vec![0, 1, 2, 0], vec![3, 4, 3]], false), elementary_cycles(&sample(), 10));
+        assert_eq!((vec![vec![0, 1, 2, 0]], true), elementary_cycles(&sample(), 1));
+        // Every pair and both triangles of a complete graph on three nodes
+        let complete = vec![vec![1, 2], vec![0, 2], vec![0, 1]];
+        assert_eq!(5, elementary_cycles(&complete, 10).0.len());
+        // 6 pairs, 4 triangles each two ways and 6 orders of all four
+        let complete: Vec<Vec<usize>> = (0..4).map(|node| (0..4).filter(|&other| other != node).collect()).collect();
+        let (cycles, more) = elementary_cycles(&complete, 100);
+        assert_eq!(20, cycles.len());
+        assert!(!more);
+    }
+}
@@ -0,0 +1,243 @@
+use std::ops::{Index, IndexMut};
+
+use crate::error::Error;
+use crate::geometry::{Direction, Point};
+
+/*
+    A rectangular grid stored row by row in a single Vec.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(width * height, cells.len(), "grid cells don't match its dimensions");
+        Grid { width, height, cells }
+    }
+
+    pub fn filled(width: usize, height: usize, value: T) -> Grid<T>
+    where T: Clone {
+        Grid { width, height, cells: vec![value; width * height] }
+    }
+
+    /*
+        Parses one row per line, converting each character with cell_fn.  Every row must be the same width.
+     */
+    pub fn parse_with<F: FnMut(char) -> T>(input: &str, mut cell_fn: F) -> Result<Grid<T>, Error> {
+        let mut width: Option<usize> = None;
+        let mut height = 0;
+        let mut cells: Vec<T> = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+            let row_width = line.chars().count();
+            match width {
+                Some(width) if width != row_width => return Err(Error::BadLine {
+                    line: idx + 1,
+                    content: line.to_string(),
+                    expected: "a row the same width as the first"
+                }),
+                _ => width = Some(row_width)
+            }
+            cells.extend(line.chars().map(&mut cell_fn));
+            height += 1;
+        }
+        Ok(Grid { width: width.unwrap_or(0), height, cells })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, position: Point) -> bool {
+        position.x >= 0 && position.y >= 0 && (position.x as usize) < self.width && (position.y as usize) < self.height
+    }
+
+    fn offset(&self, position: Point) -> Option<usize> {
+        if self.in_bounds(position) {
+            Some(position.y as usize * self.width + position.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.offset(position).map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.offset(position).map(|offset| &mut self.cells[offset])
+    }
+
+    /*
+        Replaces the cell, returning false if the position is off the grid
+     */
+    pub fn set(&mut self, position: Point, value: T) -> bool {
+        match self.get_mut(position) {
+            Some(cell) => {
+                *cell = value;
+                true
+            },
+            None => false
+        }
+    }
+
+    /*
+        Every position, row by row
+     */
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height * width).map(move |offset| Point::new((offset % width) as isize, (offset / width) as isize))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn neighbours_4(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(position, &Direction::ORTHOGONAL)
+    }
+
+    pub fn neighbours_8(&self, position: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbours(position, &Direction::ALL)
+    }
+
+    fn neighbours<'a>(&'a self, position: Point, directions: &'a [Direction]) -> impl Iterator<Item = Point> + 'a {
+        directions.iter()
+            .map(move |&direction| position.step(direction))
+            .filter(|&neighbour| self.in_bounds(neighbour))
+    }
+
+    /*
+        Every cell from start (inclusive) moving in direction until the edge of the grid
+     */
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = (Point, &T)> {
+        let mut position = start;
+        std::iter::from_fn(move || {
+            let value = self.get(position)?;
+            let current = position;
+            position = position.step(direction);
+            Some((current, value))
+        })
+    }
+
+    pub fn row(&self, row: usize) -> impl Iterator<Item = &T> {
+        self.ray(Point::new(0, row as isize), Direction::Right).map(|(_, value)| value)
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        self.ray(Point::new(col as isize, 0), Direction::Down).map(|(_, value)| value)
+    }
+
+    /*
+        The diagonals running down and to the right, starting from the bottom left corner
+     */
+    pub fn diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let starts = (0..self.height as isize).rev().map(|row| Point::new(0, row))
+            .chain((1..self.width as isize).map(|col| Point::new(col, 0)));
+        starts.map(move |start| self.ray(start, Direction::DownRight).map(|(_, value)| value))
+    }
+
+    /*
+        The diagonals running down and to the left, starting from the top left corner
+     */
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        let last_col = self.width as isize - 1;
+        let starts = (0..self.width as isize).map(|col| Point::new(col, 0))
+            .chain((1..self.height as isize).map(move |row| Point::new(last_col, row)));
+        starts.map(move |start| self.ray(start, Direction::DownLeft).map(|(_, value)| value))
+    }
+
+    pub fn find(&self, value: &T) -> Option<Point>
+    where T: PartialEq {
+        self.iter().find(|(_, cell)| *cell == value).map(|(position, _)| position)
+    }
+
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point> + 'a
+    where T: PartialEq {
+        self.iter().filter(move |(_, cell)| *cell == value).map(|(position, _)| position)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, cell_fn: F) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(cell_fn).collect() }
+    }
+}
+
+impl Grid<char> {
+
+    pub fn parse(input: &str) -> Result<Grid<char>, Error> {
+        Grid::parse_with(input, |c| c)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point) -> &T {
+        self.get(position).expect("position is off the grid")
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+
+    fn index_mut(&mut self, position: Point) -> &mut T {
+        self.get_mut(position).expect("position is off the grid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "abc\ndef\n";
+
+    #[test]
+    fn test_parse_and_index() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!(3, grid.width());
+        assert_eq!(2, grid.height());
+        assert_eq!('f', grid[Point::new(2, 1)]);
+        assert_eq!(None, grid.get(Point::new(0, 2)));
+        assert_eq!(None, grid.get(Point::new(-1, 0)));
+        assert!(matches!(Grid::parse("abc\nde\n"), Err(Error::BadLine { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        let origin = Point::ORIGIN;
+        assert_eq!(vec![Point::new(1, 0), Point::new(0, 1)], grid.neighbours_4(origin).collect::<Vec<_>>());
+        assert_eq!(vec![Point::new(1, 0), Point::new(1, 1), Point::new(0, 1)], grid.neighbours_8(origin).collect::<Vec<_>>());
+        assert_eq!(5, grid.neighbours_8(Point::new(1, 1)).count());
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!("def", grid.row(1).collect::<String>());
+        assert_eq!("cf", grid.column(2).collect::<String>());
+        let diagonals: Vec<String> = grid.diagonals().map(|diagonal| diagonal.collect()).collect();
+        assert_eq!(vec!["d", "ae", "bf", "c"], diagonals);
+        let anti_diagonals: Vec<String> = grid.anti_diagonals().map(|diagonal| diagonal.collect()).collect();
+        assert_eq!(vec!["a", "bd", "ce", "f"], anti_diagonals);
+    }
+
+    #[test]
+    fn test_find_and_set() {
+        let mut grid = Grid::parse(SAMPLE).unwrap();
+        assert_eq!(Some(Point::new(1, 1)), grid.find(&'e'));
+        assert!(grid.set(Point::new(1, 1), 'a'));
+        assert!(!grid.set(Point::new(1, 5), 'a'));
+        assert_eq!(vec![Point::new(0, 0), Point::new(1, 1)], grid.find_all(&'a').collect::<Vec<_>>());
+    }
+}
@@ -1,9 +1,86 @@
 use std::fs::File;
 use std::io::{self, BufRead};
-use std::path::Path;
+use std::str::FromStr;
 
-pub fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where P: AsRef<Path>, {
-    let file = File::open(filename)?;
-    Ok(io::BufReader::new(file).lines())
-}
\ No newline at end of file
+use crate::error::Error;
+
+/*
+    Reads the whole puzzle input.  "-" reads from stdin, anything else is a file path.
+    A file that can't be opened is an error rather than an empty input.
+ */
+pub fn read_input(input_file_name: &str) -> Result<String, Error> {
+    if input_file_name == "-" {
+        return read_from(io::stdin().lock(), "stdin");
+    }
+    let file = File::open(input_file_name)
+        .map_err(|source| Error::MissingFile { path: input_file_name.to_string(), source })?;
+    read_from(io::BufReader::new(file), input_file_name)
+}
+
+pub fn read_from<R: BufRead>(mut reader: R, source_name: &str) -> Result<String, Error> {
+    let mut return_value = String::new();
+    reader.read_to_string(&mut return_value)
+        .map_err(|source| Error::Read { path: source_name.to_string(), source })?;
+    Ok(return_value)
+}
+
+/*
+    Parses a single number, line and column are 1-based and only used for the error.
+ */
+pub fn parse_number<T: FromStr>(value: &str, line: usize, column: usize) -> Result<T, Error> {
+    value.parse().map_err(|_| Error::BadNumber { line, column, value: value.to_string() })
+}
+
+/*
+    Parses every number on a line split by separator.  Empty fields (repeated separators) are skipped.
+ */
+pub fn parse_numbers<T: FromStr>(line: &str, line_number: usize, separator: char) -> Result<Vec<T>, Error> {
+    let mut return_value: Vec<T> = Vec::new();
+    let mut offset = 0;
+    for field in line.split(separator) {
+        let trimmed = field.trim_start();
+        let column = offset + field.len() - trimmed.len() + 1;
+        let trimmed = trimmed.trim_end();
+        if !trimmed.is_empty() {
+            return_value.push(parse_number(trimmed, line_number, column)?);
+        }
+        offset += field.len() + separator.len_utf8();
+    }
+    Ok(return_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_input_missing_file() {
+        let error = read_input("./src/resources/no_such_file.txt").unwrap_err();
+        assert!(matches!(error, Error::MissingFile { .. }));
+        assert!(error.to_string().contains("no_such_file.txt"));
+    }
+
+    #[test]
+    fn test_read_from() {
+        let input = read_from("3   4\n4   3\n".as_bytes(), "inline").unwrap();
+        assert_eq!(2, input.lines().count());
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(vec![7, 6, 4], parse_numbers::<u32>("7  6 4", 1, ' ').unwrap());
+        assert_eq!(vec![75, 47, 61], parse_numbers::<u32>("75,47, 61", 1, ',').unwrap());
+    }
+
+    #[test]
+    fn test_parse_numbers_reports_position() {
+        match parse_numbers::<u32>("75,4x7,61", 12, ',') {
+            Err(Error::BadNumber { line, column, value }) => {
+                assert_eq!(12, line);
+                assert_eq!(4, column);
+                assert_eq!("4x7", value);
+            },
+            other => panic!("Unexpected result {:?}", other.map(|_| ()))
+        }
+    }
+}
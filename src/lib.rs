@@ -0,0 +1,22 @@
+pub mod answers;
+pub mod bench;
+pub mod error;
+pub mod geometry;
+pub mod graph;
+pub mod grid;
+pub mod helpers;
+pub mod parallel;
+pub mod registry;
+pub mod simulation;
+pub mod solution;
+pub mod word_search;
+
+registry::register_days! {
+    day01::Day01,
+    day02::Day02,
+    day03::Day03,
+    day04::Day04,
+    day05::Day05,
+    day06::Day06,
+    day11::Day11,
+}
@@ -1,12 +1,639 @@
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day11;
-mod helpers;
+use std::env;
+use std::io::{self, Write};
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use aoc2024::answers::{self, Answers, Outcome};
+use aoc2024::bench::Stats;
+use aoc2024::day05;
+use aoc2024::day06::{self, GuardWalk, TraceStep};
+use aoc2024::error::Error;
+use aoc2024::geometry::Point;
+use aoc2024::helpers;
+use aoc2024::parallel;
+use aoc2024::registry::{self, Parsed, Status};
+
+const USAGE: &str = "Usage:
+    aoc2024 run <day> [--part 1|2] [--input PATH|-] [--explain]
+    aoc2024 run all [--part 1|2]
+    aoc2024 list
+    aoc2024 check [day] [--answers PATH]
+    aoc2024 bench [day] [--iterations N] [--input PATH|-] [--json]
+    aoc2024 show <day> [--input PATH|-] [--colour] [--animate] [--delay MS]
+    aoc2024 trace <day> [--input PATH|-] [--format json|csv] [--obstruction X,Y]
+    aoc2024 graph <day> [--input PATH|-] [--update N]
+    aoc2024 analyse <day> [--input PATH|-] [--max-cycles N]";
+
+struct RunArgs {
+    // None runs every registered day
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    explain: bool
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    // Some(None) once "all" has been given
+    let mut day: Option<Option<u8>> = None;
+    let mut part: Option<u8> = None;
+    let mut input: Option<String> = None;
+    let mut explain = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--explain" => explain = true,
+            "--part" | "-p" => {
+                let value = iter.next().ok_or("--part needs a value")?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("Invalid part '{}', expected 1 or 2", value))
+                };
+            },
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "all" if day.is_none() => day = Some(None),
+            _ if day.is_none() => day = Some(Some(parse_day(arg)?)),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    match day {
+        Some(None) if input.is_some() => Err("--input needs a day".to_string()),
+        Some(None) if explain => Err("--explain needs a day".to_string()),
+        Some(day) => Ok(RunArgs { day, part, input, explain }),
+        None => Err("Missing day".to_string())
+    }
+}
+
+struct CheckArgs {
+    day: Option<u8>,
+    answers: String
+}
+
+fn parse_check_args(args: &[String]) -> Result<CheckArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut answers = answers::DEFAULT_ANSWERS_FILE.to_string();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--answers" | "-a" => {
+                let value = iter.next().ok_or("--answers needs a value")?;
+                answers = value.clone();
+            },
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    Ok(CheckArgs { day, answers })
+}
+
+struct BenchArgs {
+    day: Option<u8>,
+    iterations: usize,
+    input: Option<String>,
+    json: bool
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut iterations: usize = 10;
+    let mut input: Option<String> = None;
+    let mut json = false;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--iterations" | "-n" => {
+                let value = iter.next().ok_or("--iterations needs a value")?;
+                iterations = match value.parse() {
+                    Ok(count) if count > 0 => count,
+                    _ => return Err(format!("Invalid iteration count '{}'", value))
+                };
+            },
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "--json" => json = true,
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    if input.is_some() && day.is_none() {
+        return Err("--input needs a day".to_string());
+    }
+    Ok(BenchArgs { day, iterations, input, json })
+}
+
+struct ShowArgs {
+    day: u8,
+    input: Option<String>,
+    colour: bool,
+    animate: bool,
+    delay: Duration
+}
+
+fn parse_show_args(args: &[String]) -> Result<ShowArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut input: Option<String> = None;
+    let mut colour = false;
+    let mut animate = false;
+    let mut delay = Duration::from_millis(50);
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "--colour" | "--color" => colour = true,
+            "--animate" => animate = true,
+            "--delay" => {
+                let value = iter.next().ok_or("--delay needs a value")?;
+                let millis: u64 = value.parse().map_err(|_| format!("Invalid delay '{}'", value))?;
+                delay = Duration::from_millis(millis);
+            },
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    match day {
+        Some(day) => Ok(ShowArgs { day, input, colour, animate, delay }),
+        None => Err("Missing day".to_string())
+    }
+}
+
+#[derive(PartialEq)]
+enum TraceFormat {
+    JsonLines,
+    Csv
+}
+
+struct TraceArgs {
+    day: u8,
+    input: Option<String>,
+    format: TraceFormat,
+    obstruction: Option<Point>
+}
+
+fn parse_trace_args(args: &[String]) -> Result<TraceArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut input: Option<String> = None;
+    let mut format = TraceFormat::JsonLines;
+    let mut obstruction: Option<Point> = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "--format" | "-f" => {
+                let value = iter.next().ok_or("--format needs a value")?;
+                format = match value.as_str() {
+                    "json" => TraceFormat::JsonLines,
+                    "csv" => TraceFormat::Csv,
+                    _ => return Err(format!("Invalid format '{}', expected json or csv", value))
+                };
+            },
+            "--obstruction" => {
+                let value = iter.next().ok_or("--obstruction needs a value")?;
+                let coordinates: Vec<isize> = helpers::parse_numbers(value, 1, ',').map_err(|error| error.to_string())?;
+                match coordinates[..] {
+                    [x, y] => obstruction = Some(Point::new(x, y)),
+                    _ => return Err(format!("Invalid obstruction '{}', expected X,Y", value))
+                }
+            },
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    match day {
+        Some(day) => Ok(TraceArgs { day, input, format, obstruction }),
+        None => Err("Missing day".to_string())
+    }
+}
+
+struct GraphArgs {
+    day: u8,
+    input: Option<String>,
+    update: Option<usize>
+}
+
+fn parse_graph_args(args: &[String]) -> Result<GraphArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut input: Option<String> = None;
+    let mut update: Option<usize> = None;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "--update" | "-u" => {
+                let value = iter.next().ok_or("--update needs a value")?;
+                update = Some(value.parse().map_err(|_| format!("Invalid update '{}'", value))?);
+            },
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    match day {
+        Some(day) => Ok(GraphArgs { day, input, update }),
+        None => Err("Missing day".to_string())
+    }
+}
+
+struct AnalyseArgs {
+    day: u8,
+    input: Option<String>,
+    max_cycles: usize
+}
+
+fn parse_analyse_args(args: &[String]) -> Result<AnalyseArgs, String> {
+    let mut day: Option<u8> = None;
+    let mut input: Option<String> = None;
+    let mut max_cycles = 10;
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--input" | "-i" => {
+                let value = iter.next().ok_or("--input needs a value")?;
+                input = Some(value.clone());
+            },
+            "--max-cycles" => {
+                let value = iter.next().ok_or("--max-cycles needs a value")?;
+                max_cycles = value.parse().map_err(|_| format!("Invalid cycle count '{}'", value))?;
+            },
+            _ if day.is_none() => day = Some(parse_day(arg)?),
+            _ => return Err(format!("Unexpected argument '{}'", arg))
+        }
+    }
+
+    match day {
+        Some(day) => Ok(AnalyseArgs { day, input, max_cycles }),
+        None => Err("Missing day".to_string())
+    }
+}
+
+fn parse_day(arg: &str) -> Result<u8, String> {
+    arg.parse().map_err(|_| format!("Invalid day '{}'", arg))
+}
+
+const RESOURCES_DIR: &str = "./src/resources";
+
+fn default_input(day: u8) -> String {
+    format!("{}/{}", RESOURCES_DIR, default_input_name(day))
+}
+
+fn default_input_name(day: u8) -> String {
+    format!("day{:02}_input.txt", day)
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    let run_args = parse_run_args(args)?;
+    let number = match run_args.day {
+        Some(number) => number,
+        None => return run_all(run_args.part)
+    };
+    let day = registry::find(number).ok_or_else(|| format!("Day {} is not implemented", number))?;
+    let input_file_name = run_args.input.unwrap_or_else(|| default_input(number));
+    let input = helpers::read_input(&input_file_name).map_err(|error| error.to_string())?;
+    let parsed = day.parse(&input).map_err(|error| error.to_string())?;
+
+    if run_args.explain {
+        let lines = parsed.explain();
+        if lines.is_empty() {
+            eprintln!("Day {} has nothing to explain", day.number);
+        }
+        lines.iter().for_each(|line| println!("{}", line));
+    }
+    solve_parts(day, run_args.part, parsed.as_ref())?.print();
+    Ok(())
+}
+
+/*
+    A day's answers as output lines, and any warnings about its input
+ */
+struct Solved {
+    answers: Vec<String>,
+    warnings: Vec<String>
+}
+
+impl Solved {
+
+    fn print(&self) {
+        self.answers.iter().for_each(|line| println!("{}", line));
+        self.warnings.iter().for_each(|warning| eprintln!("Warning: {}", warning));
+    }
+}
+
+/*
+    Solves the requested part, or without one every part that has been attempted
+ */
+fn solve_parts(day: &registry::Day, part: Option<u8>, parsed: &dyn Parsed) -> Result<Solved, String> {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => [1, 2].into_iter().filter(|&part| day.part_status(part) != Status::Unsolved).collect()
+    };
+    let mut answers: Vec<String> = Vec::new();
+    for part in parts {
+        let answer = parsed.solve(part)
+            .map_err(|error| error.to_string())?
+            .ok_or_else(|| format!("Day {} part {} is not solved yet", day.number, part))?;
+        answers.push(format!("Day {} part {}: {}", day.number, part, answer));
+    }
+    let warnings = parsed.warnings().into_iter()
+        .map(|warning| format!("Day {}: {}", day.number, warning))
+        .collect();
+    Ok(Solved { answers, warnings })
+}
+
+/*
+    Solves every day on its own thread, printing in day order once they have all finished.
+    Like bench, a day without its input is skipped rather than stopping the run.
+ */
+fn run_all(part: Option<u8>) -> Result<(), String> {
+    let results = parallel::map(registry::all(), |day| {
+        helpers::read_input(&default_input(day.number)).map(|input| day.parse(&input)
+            .map_err(|error| error.to_string())
+            .and_then(|parsed| solve_parts(day, part, parsed.as_ref())))
+    });
+
+    let mut failed = false;
+    for (day, result) in registry::all().iter().zip(results) {
+        match result {
+            Ok(Ok(solved)) => solved.print(),
+            Ok(Err(error)) => {
+                eprintln!("{}", error);
+                failed = true;
+            },
+            Err(error) => eprintln!("Skipping day {}: {}", day.number, error)
+        }
+    }
+    if failed {
+        Err("Some days failed".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+fn list() {
+    println!("Day  {:<20}  {:<8}  Part 2", "Title", "Part 1");
+    for day in registry::all() {
+        println!("{:>3}  {:<20}  {:<8}  {}", day.number, day.title, day.status[0], day.status[1]);
+    }
+}
+
+#[derive(Default)]
+struct CheckTally {
+    passed: usize,
+    failed: usize,
+    regressions: usize,
+    unknown: usize,
+    skipped: usize
+}
+
+fn check(args: &[String]) -> Result<(), String> {
+    let check_args = parse_check_args(args)?;
+    let answers = Answers::load(&check_args.answers).map_err(|error| error.to_string())?;
+    let mut tally = CheckTally::default();
+
+    let days = registry::all().iter().filter(|day| check_args.day.is_none_or(|number| number == day.number));
+    for day in days {
+        let parts: Vec<u8> = [1, 2].into_iter().filter(|&part| day.part_status(part) != Status::Unsolved).collect();
+        let mut inputs: Vec<String> = answers.inputs(day.number).into_iter().map(String::from).collect();
+        if inputs.is_empty() {
+            inputs.push(default_input_name(day.number));
+        }
+
+        for input_name in inputs {
+            let input = helpers::read_input(&format!("{}/{}", RESOURCES_DIR, input_name));
+            let parsed = input.as_ref().map_err(|error| error.to_string())
+                .and_then(|input| day.parse(input).map_err(|error| error.to_string()));
+            for &part in &parts {
+                let label = format!("Day {:>2} part {}  {:<18}", day.number, part, input_name);
+                // Puzzle inputs aren't committed, so a checkout without one isn't a failure
+                if let Err(error @ Error::MissingFile { .. }) = &input {
+                    tally.skipped += 1;
+                    println!("{}  SKIP        {}", label, error);
+                    continue;
+                }
+                let answer = match &parsed {
+                    Ok(parsed) => parsed.solve(part).map_err(|error| error.to_string()),
+                    Err(error) => Err(error.clone())
+                };
+                match answer {
+                    Err(error) => {
+                        tally.failed += 1;
+                        println!("{}  FAIL        {}", label, error);
+                    },
+                    Ok(None) => {},
+                    Ok(Some(value)) => {
+                        match answers.check(day.number, part, &input_name, &value) {
+                            Outcome::Pass => {
+                                tally.passed += 1;
+                                println!("{}  PASS        {}", label, value);
+                            },
+                            // A complete part that no longer matches has regressed, anything else is still in progress
+                            Outcome::Mismatch { expected } if day.part_status(part) == Status::Complete => {
+                                tally.regressions += 1;
+                                println!("{}  REGRESSION  got {}, expected {}", label, value, expected);
+                            },
+                            Outcome::Mismatch { expected } => {
+                                tally.failed += 1;
+                                println!("{}  FAIL        got {}, expected {}", label, value, expected);
+                            },
+                            Outcome::Unknown => {
+                                tally.unknown += 1;
+                                println!("{}  UNKNOWN     {}", label, value);
+                            }
+                        }
+                        if let Some(rejected) = answers.rejected(day.number, part, &input_name, &value) {
+                            match &rejected.note {
+                                Some(note) => println!("    warning: {} was already rejected ({})", value, note),
+                                None => println!("    warning: {} was already rejected", value)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    println!("{} passed, {} failed, {} regressions, {} unknown, {} skipped",
+        tally.passed, tally.failed, tally.regressions, tally.unknown, tally.skipped);
+    if tally.failed + tally.regressions > 0 {
+        return Err("Check failed".to_string());
+    }
+    Ok(())
+}
+
+fn print_stats(stage: &str, stats: Option<Stats>) {
+    match stats {
+        Some(stats) => println!("    {:<8}  min {:>10.2?}  median {:>10.2?}  max {:>10.2?}", stage, stats.min, stats.median, stats.max),
+        None => println!("    {:<8}  not solved", stage)
+    }
+}
+
+fn bench(args: &[String]) -> Result<(), String> {
+    let bench_args = parse_bench_args(args)?;
+    let days: Vec<&registry::Day> = match bench_args.day {
+        Some(number) => vec![registry::find(number).ok_or_else(|| format!("Day {} is not implemented", number))?],
+        None => registry::all().iter().collect()
+    };
+
+    let mut json_entries: Vec<String> = Vec::new();
+    for day in days {
+        let input_file_name = bench_args.input.clone().unwrap_or_else(|| default_input(day.number));
+        // When benchmarking every day, a day without its input is skipped rather than stopping the run
+        let timings = helpers::read_input(&input_file_name).and_then(|input| day.bench(&input, bench_args.iterations));
+        let timings = match timings {
+            Ok(timings) => timings,
+            Err(error) if bench_args.day.is_none() => {
+                eprintln!("Skipping day {}: {}", day.number, error);
+                continue;
+            },
+            Err(error) => return Err(error.to_string())
+        };
+
+        if bench_args.json {
+            json_entries.push(timings.to_json(day.number, day.title));
+        } else {
+            println!("Day {:>2}  {}  ({} iterations)", day.number, day.title, timings.iterations);
+            print_stats("parse", Some(timings.parse));
+            print_stats("part 1", timings.part_one);
+            print_stats("part 2", timings.part_two);
+        }
+    }
+
+    if bench_args.json {
+        println!("[{}]", json_entries.join(",\n"));
+    }
+    Ok(())
+}
+
+/*
+    Draws a day's puzzle state.  Only day 6 has a visualisation, the guard's walk.
+ */
+fn show(args: &[String]) -> Result<(), String> {
+    let show_args = parse_show_args(args)?;
+    if show_args.day != 6 {
+        return Err(format!("Day {} has no visualisation", show_args.day));
+    }
+    let input_file_name = show_args.input.unwrap_or_else(|| default_input(show_args.day));
+    let input = helpers::read_input(&input_file_name).map_err(|error| error.to_string())?;
+    let walk = GuardWalk::parse(&input).map_err(|error| error.to_string())?;
+
+    if show_args.animate {
+        for frame in 0..walk.len() {
+            // Clear the screen and move the cursor home before each frame
+            print!("\x1b[2J\x1b[H{}", walk.render_frame(frame, show_args.colour));
+            io::stdout().flush().map_err(|error| error.to_string())?;
+            thread::sleep(show_args.delay);
+        }
+        print!("\x1b[2J\x1b[H");
+    }
+    print!("{}", walk.render(show_args.colour));
+    Ok(())
+}
+
+/*
+    Writes every step of a day's simulation, one record per line.  Only day 6 can be traced.
+ */
+fn trace(args: &[String]) -> Result<(), String> {
+    let trace_args = parse_trace_args(args)?;
+    if trace_args.day != 6 {
+        return Err(format!("Day {} has no trace", trace_args.day));
+    }
+    let input_file_name = trace_args.input.unwrap_or_else(|| default_input(trace_args.day));
+    let input = helpers::read_input(&input_file_name).map_err(|error| error.to_string())?;
+    let steps = day06::trace(&input, trace_args.obstruction).map_err(|error| error.to_string())?;
+
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    let write_error = |error: io::Error| error.to_string();
+    if trace_args.format == TraceFormat::Csv {
+        writeln!(out, "{}", TraceStep::CSV_HEADER).map_err(write_error)?;
+    }
+    for step in steps {
+        let line = match trace_args.format {
+            TraceFormat::JsonLines => step.to_json(),
+            TraceFormat::Csv => step.to_csv()
+        };
+        writeln!(out, "{}", line).map_err(write_error)?;
+    }
+    out.flush().map_err(write_error)
+}
+
+/*
+    Prints a day's structure as a Graphviz graph.  Only day 5, the page ordering rules, has one.
+ */
+fn graph(args: &[String]) -> Result<(), String> {
+    let graph_args = parse_graph_args(args)?;
+    if graph_args.day != 5 {
+        return Err(format!("Day {} has no graph", graph_args.day));
+    }
+    let input_file_name = graph_args.input.unwrap_or_else(|| default_input(graph_args.day));
+    let input = helpers::read_input(&input_file_name).map_err(|error| error.to_string())?;
+    print!("{}", day05::rules_to_dot(&input, graph_args.update).map_err(|error| error.to_string())?);
+    Ok(())
+}
+
+/*
+    Reports on the structure of a day's input.  Only day 5's rules have an analysis.
+ */
+fn analyse(args: &[String]) -> Result<(), String> {
+    let analyse_args = parse_analyse_args(args)?;
+    if analyse_args.day != 5 {
+        return Err(format!("Day {} has no analysis", analyse_args.day));
+    }
+    let input_file_name = analyse_args.input.unwrap_or_else(|| default_input(analyse_args.day));
+    let input = helpers::read_input(&input_file_name).map_err(|error| error.to_string())?;
+    let analysis = day05::analyse(&input, analyse_args.max_cycles).map_err(|error| error.to_string())?;
+    analysis.report().iter().for_each(|line| println!("{}", line));
+    Ok(())
+}
 
 fn main() {
-    println!("Hello, world!");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|arg| arg.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("check") => check(&args[1..]),
+        Some("bench") => bench(&args[1..]),
+        Some("show") => show(&args[1..]),
+        Some("trace") => trace(&args[1..]),
+        Some("graph") => graph(&args[1..]),
+        Some("analyse") | Some("analyze") => analyse(&args[1..]),
+        Some("list") => {
+            list();
+            Ok(())
+        },
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        Some(command) => Err(format!("Unknown command '{}'\n{}", command, USAGE)),
+        None => Err(USAGE.to_string())
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
 }
@@ -0,0 +1,55 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+pub fn thread_count() -> usize {
+    thread::available_parallelism().map_or(1, |count| count.get())
+}
+
+/*
+    Maps every item on a pool of scoped threads and returns the results in the same order as the items,
+    so callers get exactly what a sequential map would give.  Threads take the next unclaimed item
+    whenever they finish one, which keeps them busy when some items take much longer than others.
+ */
+pub fn map<T, R, F>(items: &[T], map_fn: F) -> Vec<R>
+where T: Sync, R: Send, F: Fn(&T) -> R + Sync {
+    let threads = thread_count().min(items.len());
+    if threads <= 1 {
+        return items.iter().map(map_fn).collect();
+    }
+
+    let next_item = AtomicUsize::new(0);
+    let results: Mutex<Vec<(usize, R)>> = Mutex::new(Vec::with_capacity(items.len()));
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                let mut local_results: Vec<(usize, R)> = Vec::new();
+                loop {
+                    let idx = next_item.fetch_add(1, Ordering::Relaxed);
+                    if idx >= items.len() {
+                        break;
+                    }
+                    local_results.push((idx, map_fn(&items[idx])));
+                }
+                results.lock().unwrap().extend(local_results);
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|(idx, _)| *idx);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let items: Vec<u64> = (0..1000).collect();
+        let expected: Vec<u64> = items.iter().map(|value| value * value).collect();
+        assert_eq!(expected, map(&items, |value| value * value));
+        assert!(map(&Vec::<u64>::new(), |value| *value).is_empty());
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+use crate::bench::{self, Timings};
+use crate::error::Error;
+use crate::solution::Solution;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Complete,
+    Partial,
+    Unsolved
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let label = match self {
+            Status::Complete => "complete",
+            Status::Partial => "partial",
+            Status::Unsolved => "unsolved"
+        };
+        f.pad(label)
+    }
+}
+
+/*
+    A day's input once parsed, so running both parts, warnings and explain parses it only once
+ */
+pub trait Parsed {
+    fn solve(&self, part: u8) -> Result<Option<String>, Error>;
+
+    fn warnings(&self) -> Vec<String>;
+
+    fn explain(&self) -> Vec<String>;
+}
+
+impl<S: Solution> Parsed for S {
+
+    fn solve(&self, part: u8) -> Result<Option<String>, Error> {
+        let answer = match part {
+            1 => self.part_one()?,
+            _ => self.part_two()?
+        };
+        Ok(answer.map(|value| value.to_string()))
+    }
+
+    fn warnings(&self) -> Vec<String> {
+        Solution::warnings(self)
+    }
+
+    fn explain(&self) -> Vec<String> {
+        Solution::explain(self)
+    }
+}
+
+/*
+    A registered day.  Built from a Solution so the metadata lives next to the code.
+ */
+pub struct Day {
+    pub number: u8,
+    pub title: &'static str,
+    pub status: [Status; 2],
+    parse_fn: fn(&str) -> Result<Box<dyn Parsed>, Error>,
+    bench_fn: fn(&str, usize) -> Result<Timings, Error>
+}
+
+impl Day {
+    pub const fn of<S: Solution + 'static>() -> Day {
+        Day {
+            number: S::DAY,
+            title: S::TITLE,
+            status: S::STATUS,
+            parse_fn: parse_input::<S>,
+            bench_fn: bench::time_solution::<S>
+        }
+    }
+
+    pub fn part_status(&self, part: u8) -> Status {
+        self.status[part as usize - 1]
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, Error> {
+        (self.parse_fn)(input)
+    }
+
+    pub fn bench(&self, input: &str, iterations: usize) -> Result<Timings, Error> {
+        (self.bench_fn)(input, iterations)
+    }
+}
+
+fn parse_input<S: Solution + 'static>(input: &str) -> Result<Box<dyn Parsed>, Error> {
+    Ok(Box::new(S::parse(input)?))
+}
+
+/*
+    Declares the day modules and registers their solutions in one place.
+    Adding a day is one line here, everything else is discovered from the list.
+ */
+macro_rules! register_days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub static DAYS: &[registry::Day] = &[
+            $(registry::Day::of::<$module::$solution>()),*
+        ];
+    };
+}
+
+pub(crate) use register_days;
+
+pub fn all() -> &'static [Day] {
+    crate::DAYS
+}
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    all().iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_unique_and_ordered() {
+        let numbers: Vec<u8> = all().iter().map(|day| day.number).collect();
+        assert!(numbers.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(find(1).is_some());
+        assert!(find(25).is_none());
+    }
+
+    #[test]
+    fn test_parse_once_for_every_stage() {
+        let parsed = find(1).unwrap().parse(include_str!("resources/day01_simple.txt")).unwrap();
+        assert_eq!(Some("11".to_string()), parsed.solve(1).unwrap());
+        assert_eq!(Some("31".to_string()), parsed.solve(2).unwrap());
+        assert!(parsed.warnings().is_empty());
+    }
+}
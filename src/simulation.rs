@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/*
+    A cycle in a sequence of states.  The state at step start is the first one that repeats,
+    and it comes round again every length steps.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome<S> {
+    // The transition returned None after this many steps, leaving the simulation in final_state
+    Halted { steps: usize, final_state: S },
+    Cycle(Cycle)
+}
+
+/*
+    Steps the state until the transition halts (returns None) or a state repeats.
+    Every state is remembered, so this suits simulations that can end as well as loop.
+ */
+pub fn detect_cycle<S, F>(initial: S, mut step_fn: F) -> Outcome<S>
+where S: Hash + Eq + Clone, F: FnMut(&S) -> Option<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    let mut step = 0;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            return Outcome::Cycle(Cycle { start, length: step - start });
+        }
+        let next = step_fn(&state);
+        match next {
+            Some(next) => {
+                seen.insert(state, step);
+                state = next;
+                step += 1;
+            },
+            None => return Outcome::Halted { steps: step, final_state: state }
+        }
+    }
+}
+
+/*
+    Brent's algorithm for a transition that never halts.  Only a couple of states are kept,
+    which makes it the better choice when states are large and the cycle is long.
+ */
+pub fn brent<S, F>(initial: S, mut step_fn: F) -> Cycle
+where S: Eq + Clone, F: FnMut(&S) -> S {
+    // Find the cycle length by moving the hare in growing powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step_fn(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step_fn(&hare);
+        length += 1;
+    }
+
+    // With the hare length steps ahead, both meet at the start of the cycle
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step_fn(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step_fn(&tortoise);
+        hare = step_fn(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 ...
+    fn rho(state: &u32) -> u32 {
+        if *state == 5 { 2 } else { state + 1 }
+    }
+
+    #[test]
+    fn test_detect_cycle() {
+        assert_eq!(Outcome::Cycle(Cycle { start: 2, length: 4 }), detect_cycle(0, |state| Some(rho(state))));
+        assert_eq!(Outcome::Halted { steps: 3, final_state: 3 }, detect_cycle(0, |&state| if state < 3 { Some(state + 1) } else { None }));
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(Cycle { start: 2, length: 4 }, brent(0, rho));
+        assert_eq!(Cycle { start: 0, length: 1 }, brent(7, |&state| state));
+        // x -> x^2 + 1 mod 255 from 3, matches the hash based result
+        let step = |state: &u64| (state * state + 1) % 255;
+        assert_eq!(Outcome::Cycle(brent(3, step)), detect_cycle(3, |state| Some(step(state))));
+    }
+}
@@ -0,0 +1,47 @@
+use std::fmt::Display;
+use std::io::BufRead;
+
+use crate::error::Error;
+use crate::helpers;
+use crate::registry::Status;
+
+/*
+    A day's puzzle.  The input text is parsed once and then shared by both parts.
+    A part returns None when it has not been solved yet, and an error when the input turns out to
+    be inconsistent only once the part tries to solve it.
+ */
+pub trait Solution: Sized {
+    const DAY: u8;
+    const TITLE: &'static str;
+    const STATUS: [Status; 2];
+
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self, Error>;
+
+    fn load(input_file_name: &str) -> Result<Self, Error> {
+        Self::parse(&helpers::read_input(input_file_name)?)
+    }
+
+    fn read<R: BufRead>(reader: R) -> Result<Self, Error> {
+        Self::parse(&helpers::read_from(reader, "reader")?)
+    }
+
+    fn part_one(&self) -> Result<Option<Self::Answer>, Error>;
+
+    fn part_two(&self) -> Result<Option<Self::Answer>, Error>;
+
+    /*
+        Anything about the input that makes the answers doubtful, reported alongside them
+     */
+    fn warnings(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /*
+        Lines describing how the answers were reached, for checking the input was understood
+     */
+    fn explain(&self) -> Vec<String> {
+        Vec::new()
+    }
+}
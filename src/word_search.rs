@@ -0,0 +1,229 @@
+use std::collections::BTreeSet;
+
+use crate::geometry::{Direction, Point};
+use crate::grid::Grid;
+
+/*
+    A word found in the grid, reading from start in direction
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordMatch {
+    pub start: Point,
+    pub direction: Direction,
+    pub cells: Vec<Point>
+}
+
+/*
+    How a pattern was turned before matching: quarter turns clockwise, applied after an optional left/right mirror
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    pub quarter_turns: u8,
+    pub mirrored: bool
+}
+
+/*
+    A pattern found in the grid.  anchor is where the top left corner of the oriented pattern's bounding box landed.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternMatch {
+    pub anchor: Point,
+    pub orientation: Orientation,
+    pub cells: Vec<Point>
+}
+
+/*
+    A 2D stencil of characters.  Wildcard cells are left out, so they match anything (including off the grid).
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    cells: Vec<(Point, char)>
+}
+
+impl Pattern {
+
+    pub fn stencil(text: &str, wildcard: char) -> Pattern {
+        let mut cells: Vec<(Point, char)> = Vec::new();
+        for (row, line) in text.lines().enumerate() {
+            for (col, c) in line.chars().enumerate() {
+                if c != wildcard {
+                    cells.push((Point::new(col as isize, row as isize), c));
+                }
+            }
+        }
+        Pattern { cells }.normalised()
+    }
+
+    pub fn word(word: &str) -> Pattern {
+        Pattern::stencil(word, '\0')
+    }
+
+    pub fn cells(&self) -> &[(Point, char)] {
+        &self.cells
+    }
+
+    /*
+        Moves the pattern so its bounding box starts at the origin, with cells in a fixed order so variants compare equal
+     */
+    fn normalised(mut self) -> Pattern {
+        let min_x = self.cells.iter().map(|(point, _)| point.x).min().unwrap_or(0);
+        let min_y = self.cells.iter().map(|(point, _)| point.y).min().unwrap_or(0);
+        for (point, _) in self.cells.iter_mut() {
+            *point -= Point::new(min_x, min_y);
+        }
+        self.cells.sort();
+        self
+    }
+
+    pub fn rotated(&self) -> Pattern {
+        // A quarter turn clockwise with y pointing down
+        let cells = self.cells.iter().map(|&(point, c)| (Point::new(-point.y, point.x), c)).collect();
+        Pattern { cells }.normalised()
+    }
+
+    pub fn mirrored(&self) -> Pattern {
+        let cells = self.cells.iter().map(|&(point, c)| (Point::new(-point.x, point.y), c)).collect();
+        Pattern { cells }.normalised()
+    }
+
+    pub fn oriented(&self, orientation: Orientation) -> Pattern {
+        let mut return_value = if orientation.mirrored { self.mirrored() } else { self.clone() };
+        for _ in 0..orientation.quarter_turns % 4 {
+            return_value = return_value.rotated();
+        }
+        return_value
+    }
+
+    /*
+        Every distinct rotation and mirror image.  A symmetric pattern has fewer than eight.
+     */
+    pub fn variants(&self) -> Vec<(Orientation, Pattern)> {
+        let mut return_value: Vec<(Orientation, Pattern)> = Vec::new();
+        for mirrored in [false, true] {
+            for quarter_turns in 0..4 {
+                let orientation = Orientation { quarter_turns, mirrored };
+                let variant = self.oriented(orientation);
+                if !return_value.iter().any(|(_, existing)| *existing == variant) {
+                    return_value.push((orientation, variant));
+                }
+            }
+        }
+        return_value
+    }
+
+    pub fn matches_at(&self, grid: &Grid<char>, anchor: Point) -> bool {
+        self.cells.iter().all(|&(offset, c)| grid.get(anchor + offset) == Some(&c))
+    }
+}
+
+/*
+    Whether word reads from start going in direction.  Running off the grid is never a match.
+ */
+pub fn matches_word(grid: &Grid<char>, start: Point, direction: Direction, word: &str) -> bool {
+    grid.ray(start, direction).map(|(_, &c)| c).take(word.chars().count()).eq(word.chars())
+}
+
+/*
+    Every occurrence of word in all eight directions, overlaps included.  A palindrome is found twice, once each way.
+ */
+pub fn find_word(grid: &Grid<char>, word: &str) -> Vec<WordMatch> {
+    let first = match word.chars().next() {
+        Some(c) => c,
+        None => return Vec::new()
+    };
+    let length = word.chars().count() as isize;
+
+    let mut return_value: Vec<WordMatch> = Vec::new();
+    for start in grid.find_all(&first) {
+        for direction in Direction::ALL {
+            if matches_word(grid, start, direction, word) {
+                let cells = (0..length).map(|idx| start + direction.unit() * idx).collect();
+                return_value.push(WordMatch { start, direction, cells });
+            }
+        }
+    }
+    return_value
+}
+
+/*
+    Every placement of the pattern in any of its orientations
+ */
+pub fn find_pattern(grid: &Grid<char>, pattern: &Pattern) -> Vec<PatternMatch> {
+    if pattern.cells.is_empty() {
+        return Vec::new();
+    }
+
+    let mut return_value: Vec<PatternMatch> = Vec::new();
+    for (orientation, variant) in pattern.variants() {
+        for anchor in grid.positions() {
+            if variant.matches_at(grid, anchor) {
+                let cells = variant.cells.iter().map(|&(offset, _)| anchor + offset).collect();
+                return_value.push(PatternMatch { anchor, orientation, cells });
+            }
+        }
+    }
+    return_value
+}
+
+/*
+    Renders the grid with every cell outside the given ones replaced by '.', like the puzzle examples
+ */
+pub fn highlight<I: IntoIterator<Item = Point>>(grid: &Grid<char>, cells: I) -> String {
+    let keep: BTreeSet<Point> = cells.into_iter().collect();
+    let mut return_value = String::new();
+    for row in 0..grid.height() {
+        for col in 0..grid.width() {
+            let position = Point::new(col as isize, row as isize);
+            return_value.push(if keep.contains(&position) { grid[position] } else { '.' });
+        }
+        return_value.push('\n');
+    }
+    return_value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_matches_word() {
+        let grid = Grid::parse("XMASASAMXFH").unwrap();
+
+        assert!(matches_word(&grid, Point::new(0, 0), Direction::Right, "XMAS"));
+        assert!(!matches_word(&grid, Point::new(3, 0), Direction::Right, "XMAS"));
+        assert!(!matches_word(&grid, Point::new(9, 0), Direction::Right, "XMAS"));
+        assert!(matches_word(&grid, Point::new(8, 0), Direction::Left, "XMAS"));
+        assert!(!matches_word(&grid, Point::new(2, 0), Direction::Left, "XMAS"));
+    }
+
+    #[test]
+    fn check_find_word() {
+        let grid = Grid::parse("S..S..S\n.A.A.A.\n..MMM..\nSAMXMAS\n..MMM..\n.A.A.A.\nS..S..S\n").unwrap();
+        let matches = find_word(&grid, "XMAS");
+        assert_eq!(8, matches.len());
+        let up = matches.iter().find(|found| found.direction == Direction::Up).unwrap();
+        assert_eq!(vec![Point::new(3, 3), Point::new(3, 2), Point::new(3, 1), Point::new(3, 0)], up.cells);
+        assert!(find_word(&grid, "").is_empty());
+    }
+
+    #[test]
+    fn check_variants() {
+        let x_mas = Pattern::stencil("M.S\n.A.\nM.S", '.');
+        assert_eq!(4, x_mas.variants().len());
+        let corner = Pattern::stencil("AB\nC.", '.');
+        assert_eq!(8, corner.variants().len());
+        assert_eq!(Pattern::stencil("CA\n.B", '.'), corner.rotated());
+        assert_eq!(Pattern::stencil("BA\n.C", '.'), corner.mirrored());
+        assert_eq!(4, Pattern::word("XMAS").variants().len());
+    }
+
+    #[test]
+    fn check_find_pattern() {
+        let grid = Grid::parse("S.S.\n.A..\nM.M.\n....\n").unwrap();
+        let matches = find_pattern(&grid, &Pattern::stencil("M.S\n.A.\nM.S", '.'));
+        assert_eq!(1, matches.len());
+        assert_eq!(Point::new(0, 0), matches[0].anchor);
+        assert_eq!(Orientation { quarter_turns: 3, mirrored: false }, matches[0].orientation);
+        assert_eq!("S.S.\n.A..\nM.M.\n....\n", highlight(&grid, matches[0].cells.clone()));
+    }
+}